@@ -0,0 +1,30 @@
+//! Times injection scoring of clean text with and without the combined-rule
+//! prefilter (`ScanOptions::combined_prefilter`).
+//!
+//! Run with `cargo bench --bench combined_prefilter`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use _core::injection_detector::{score, ScanOptions};
+
+const ITERATIONS: u32 = 2_000;
+
+fn main() {
+    let clean = "Summarise the quarterly report and list the key risks. ".repeat(40);
+    for combined_prefilter in [false, true] {
+        let options = ScanOptions {
+            combined_prefilter,
+            ..ScanOptions::default()
+        };
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(score(black_box(&clean), &options));
+        }
+        println!(
+            "combined_prefilter={}: {:?} per call",
+            combined_prefilter,
+            start.elapsed() / ITERATIONS
+        );
+    }
+}
@@ -4,16 +4,48 @@ Core guardrail logic is implemented in Rust (via PyO3) for performance.
 Python wrappers expose the same API as the original pure-Python version.
 """
 
+from llm_guardrails._core import (
+    CustomStateSnapshot,
+    InjectionVerdict,
+    RuleStats,
+    get_batch_threads,
+    get_score_precision,
+    reset_all_custom_state,
+    reset_custom_rules,
+    restore_custom_state,
+    set_batch_threads,
+    set_score_precision,
+    snapshot_custom_state,
+)
 from llm_guardrails.bias_scorer import BiasScorer
+from llm_guardrails.hidden_unicode import contains_hidden_unicode
 from llm_guardrails.injection_detector import InjectionDetector
-from llm_guardrails.output_validator import OutputValidator
-from llm_guardrails.pii_redactor import PIIRedactor
+from llm_guardrails.log_sanitizer import sanitize_for_log
+from llm_guardrails.output_validator import OutputValidator, StreamingOutputValidator
+from llm_guardrails.pii_redactor import PIIRedactor, StreamingRedactor
 from llm_guardrails.pipeline import GuardrailsPipeline
+from llm_guardrails.risk_summary import risk_summary
 
 __all__ = [
     "PIIRedactor",
+    "StreamingRedactor",
     "InjectionDetector",
+    "InjectionVerdict",
     "BiasScorer",
     "OutputValidator",
+    "StreamingOutputValidator",
     "GuardrailsPipeline",
+    "RuleStats",
+    "sanitize_for_log",
+    "contains_hidden_unicode",
+    "risk_summary",
+    "get_score_precision",
+    "set_score_precision",
+    "get_batch_threads",
+    "set_batch_threads",
+    "CustomStateSnapshot",
+    "snapshot_custom_state",
+    "restore_custom_state",
+    "reset_custom_rules",
+    "reset_all_custom_state",
 ]
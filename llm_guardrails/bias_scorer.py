@@ -9,6 +9,9 @@ from __future__ import annotations
 from pydantic import BaseModel
 
 from llm_guardrails._core import bias_score as _bias_score
+from llm_guardrails._core import bias_score_bp as _bias_score_bp
+from llm_guardrails._core import bias_validate as _bias_validate
+from llm_guardrails.output_validator import ValidationIssue
 
 
 class BiasReport(BaseModel):
@@ -20,20 +23,95 @@ class BiasReport(BaseModel):
     flags: list[str]
     """Human-readable descriptions of each detected signal."""
 
+    normalized_text: str | None = None
+    """The text actually scanned, after zero-width and bidi-control
+    characters were stripped (only with ``return_normalized``)."""
+
 
 class BiasScorer:
     """Score LLM output for demographic bias indicators.
 
     The implementation checks three categories:
 
-    1. **Stereotyping language** -- phrases that reinforce common stereotypes.
+    1. **Stereotyping language** -- phrases that reinforce common stereotypes,
+       including loaded adjectives framing a group ("lazy immigrants").
     2. **Unbalanced demographic references** -- significant asymmetry in
        how often different demographic groups are mentioned.
     3. **Absolute generalisation markers** -- language such as "all X are ..."
        that paints entire groups with a single brush.
+
+    Parameters
+    ----------
+    occupational_association : bool
+        Also flag occupation terms appearing near a gendered pronoun
+        ("the engineer fixed it himself").
+    occupations : list[str] | None
+        Occupation terms for that check; defaults to a built-in list.
+    token_groups : dict[str, list[str]] | None
+        Named demographic token groups; reference imbalance is measured
+        between the most- and least-referenced group instead of the
+        built-in male/female lists.
+    loaded_adjectives : list[str] | None
+        Negatively loaded adjectives flagged when they directly modify a
+        demographic noun; defaults to a built-in lexicon.
+    dedupe_overlapping : bool
+        Flag and score signals whose matched text overlaps (``"All women
+        are ..."`` is both a stereotype and a generalisation) once, as the
+        most severe of them.
+    unified_scale : bool
+        Report scores on the scale shared with :class:`InjectionDetector`
+        and the output validator's hallucination score, where ``0.5`` is
+        each detector's default threshold.
     """
 
-    def score(self, text: str) -> BiasReport:
-        """Analyse *text* and return a :class:`BiasReport`."""
-        score_val, flags = _bias_score(text)
-        return BiasReport(score=score_val, flags=flags)
+    def __init__(
+        self,
+        occupational_association: bool = False,
+        occupations: list[str] | None = None,
+        token_groups: dict[str, list[str]] | None = None,
+        loaded_adjectives: list[str] | None = None,
+        dedupe_overlapping: bool = False,
+        unified_scale: bool = False,
+    ) -> None:
+        self.occupational_association = occupational_association
+        self.occupations = occupations
+        self.token_groups = token_groups
+        self.loaded_adjectives = loaded_adjectives
+        self.dedupe_overlapping = dedupe_overlapping
+        self.unified_scale = unified_scale
+
+    def score(self, text: str, return_normalized: bool = False) -> BiasReport:
+        """Analyse *text* and return a :class:`BiasReport`.
+
+        With *return_normalized*, the report includes the text the scorer
+        actually matched against, which helps when investigating evasions.
+        """
+        score_val, flags, normalized = _bias_score(
+            text, return_normalized=return_normalized, **self._options()
+        )
+        return BiasReport(score=score_val, flags=flags, normalized_text=normalized)
+
+    def score_bp(self, text: str) -> int:
+        """Return the bias score as integer basis points (0--10000)."""
+        return _bias_score_bp(text, **self._options())[0]
+
+    def validate(self, text: str, threshold: float) -> tuple[bool, list[ValidationIssue]]:
+        """Use bias as a hard gate: fail when the score reaches *threshold*.
+
+        Returns ``(is_valid, issues)`` in the same shape as
+        :class:`~llm_guardrails.output_validator.OutputValidator` -- a
+        ``bias`` error when the gate trips, plus one ``bias_signal`` warning
+        per detected flag.
+        """
+        is_valid, issues_raw = _bias_validate(text, threshold, **self._options())
+        return is_valid, [ValidationIssue(**i) for i in issues_raw]
+
+    def _options(self) -> dict[str, object]:
+        return {
+            "occupational_association": self.occupational_association,
+            "occupations": self.occupations,
+            "token_groups": self.token_groups,
+            "loaded_adjectives": self.loaded_adjectives,
+            "dedupe_overlapping": self.dedupe_overlapping,
+            "unified_scale": self.unified_scale,
+        }
@@ -0,0 +1,22 @@
+"""Detection of zero-width and bidirectional-override characters.
+
+Thin Python wrapper around the Rust ``_core`` implementation.
+"""
+
+from __future__ import annotations
+
+from llm_guardrails._core import contains_hidden_unicode as _contains_hidden_unicode
+
+__all__ = ["contains_hidden_unicode"]
+
+
+def contains_hidden_unicode(text: str) -> list[tuple[str, int]]:
+    """Return each invisible or bidi-control character in *text*.
+
+    Covers zero-width characters (U+200B-U+200F), bidi embeddings and
+    overrides (U+202A-U+202E), bidi isolates (U+2066-U+2069) and the BOM
+    (U+FEFF).  Each hit is ``(code_point, byte_offset)`` such as
+    ``("U+202E", 8)``; bidi overrides can make text display in a different
+    order than it is processed ("trojan source").
+    """
+    return _contains_hidden_unicode(text)
@@ -9,12 +9,42 @@ from __future__ import annotations
 from pydantic import BaseModel
 
 from llm_guardrails._core import (
-    injection_analyse,
+    InjectionVerdict,
+    RuleStats,
+    ScanConfig,
+    contains_whitespace_padding,
+    injection_analyse_detailed,
+    injection_analyse_v2,
+    injection_analyse_with_rules,
+    injection_classify,
     injection_list_rules,
+    injection_register_rule,
     injection_score,
+    injection_scan_structured,
+    injection_segments,
+    injection_score_bp,
 )
 
 
+class InjectionMatch(BaseModel):
+    """One match of a fired rule, located in the original input."""
+
+    label: str
+    """Label of the rule that matched."""
+
+    start: int
+    """Byte offset of the match in the input."""
+
+    end: int
+    """Byte offset just past the match."""
+
+    text: str
+    """The matched substring of the input."""
+
+    weight: float
+    """Weight of the rule."""
+
+
 class InjectionResult(BaseModel):
     """Result of an injection-detection scan."""
 
@@ -27,38 +57,329 @@ class InjectionResult(BaseModel):
     matched_rules: list[str]
     """Human-readable labels of the rules that fired."""
 
+    matched_rule_ids: list[str] = []
+    """Stable IDs of the rules that fired, parallel to ``matched_rules``.
+    Unlike labels, IDs never change, so policies should key on them."""
+
+    action_hint: str = "allow"
+    """Suggested remediation: ``allow``, ``flag_for_review``,
+    ``strip_and_retry`` or ``reject``."""
+
+    severity: str = "none"
+    """Worst severity among the matched rules: ``none``, ``low``,
+    ``medium``, ``high`` or ``critical``."""
+
+    matches: list[InjectionMatch] = []
+    """Every match of a fired rule with its location in the input, even
+    when normalization changed the text that was scanned."""
+
+    normalized_text: str | None = None
+    """The text actually scanned, after zero-width and bidi-control
+    characters were stripped (only with ``return_normalized``)."""
+
 
 class InjectionDetector:
     """Score and classify user prompts for injection risk.
 
+    The scan options (``scan_last_sentences`` through ``multi_match_bonus``)
+    apply to every scoring method, not only :meth:`analyse`; invalid ones
+    raise ``ValueError`` from whichever method is called.
+
     Parameters
     ----------
     threshold : float
         Score at or above which ``detect`` returns ``True``.
         Defaults to ``0.5``.
+    action_overrides : dict[str, str] | None
+        Per-rule remediation actions replacing the defaults reported by
+        :meth:`list_rules`.
+    stats : RuleStats | None
+        Optional collector that counts which rules fire across calls.
+    scan_last_sentences : int | None
+        Have :meth:`analyse` score only the last N sentences at full weight.
+        Matches in earlier (e.g. pasted) content are down-weighted and
+        reported as ``earlier_context``.  ``0`` scores the whole text.
+    severity_overrides : dict[str, str] | None
+        Per-rule severities (``low``/``medium``/``high``/``critical``)
+        replacing the defaults reported by :meth:`list_rules`.
+    proximity_window : int | None
+        Distance in bytes within which two distinct matched rules earn an
+        extra proximity bonus in :meth:`analyse`; defaults to 80.
+    band_cutoffs : tuple[float, float, float] | None
+        Lowest scores of the ``suspicious``, ``likely`` and ``certain``
+        bands used by :meth:`classify`; defaults to ``(0.3, 0.6, 0.9)``.
+    density_weighting : bool
+        Have :meth:`analyse` scale the score by the fraction of the input
+        covered by matched rules, so an injection phrase that makes up the
+        whole message scores higher than one buried in a long document.
+    unified_scale : bool
+        Report scores on the scale shared with :class:`BiasScorer` and the
+        output validator's hallucination score, where ``0.5`` is each
+        detector's default threshold, so one threshold fits all of them.
+        *threshold* is then read on that scale too.
+    aggregation : str
+        How :meth:`analyse` combines several matched rules: ``"max"``
+        (default) takes the heaviest rule plus a flat multi-match bonus;
+        ``"sum"`` combines every weight as ``1 - prod(1 - w)``, so many
+        moderate signals can outscore a single strong one.
+    decode_payloads : bool
+        Have :meth:`analyse` decode base64 runs (16 to 4096 characters) and
+        scan the decoded text too, so ``ignore previous instructions``
+        hidden in a blob still fires.  Hits are also reported as
+        ``decoded_payload``.
+    normalize : bool
+        Strip zero-width characters and fold confusable homoglyphs (Cyrillic
+        ``і``, fullwidth letters, ...) to ASCII before matching, so spoofed
+        keywords still fire.  On by default.
+    deleet : bool
+        Read leetspeak (``0``/``1``/``3``/``4``/``5``/``7``/``@``) as letters
+        inside tokens that also contain a letter, so ``1gn0re`` matches like
+        ``ignore``; standalone numbers are left alone.
+    disabled_rules : list[str] | None
+        Rule labels (see :meth:`list_rules`) that :meth:`analyse` ignores,
+        e.g. ``["role_play_attack"]`` for creative-writing prompts.  Unknown
+        labels raise ``ValueError``.
+    allowlist : list[str] | None
+        Phrases known to be benign, e.g. documentation that quotes
+        ``reveal the system prompt`` as an example.  A rule match lying
+        entirely inside an occurrence of one (case-insensitive) is ignored;
+        the same pattern elsewhere in the text still fires.
+    weight_overrides : dict[str, float] | None
+        Rule labels mapped to weights used instead of the built-in ones, to
+        tune sensitivity per deployment, e.g. ``{"role_play_attack": 0.3}``.
+        Weights are clamped to ``[0, 1]``; unknown labels raise
+        ``ValueError``.
+    multi_match_bonus : float | None
+        Replaces the ``0.10`` added when two or more rules match (clamped
+        to ``[0, 1]``).  ``None`` keeps the default.
     """
 
-    def __init__(self, threshold: float = 0.5) -> None:
+    def __init__(
+        self,
+        threshold: float = 0.5,
+        action_overrides: dict[str, str] | None = None,
+        stats: RuleStats | None = None,
+        scan_last_sentences: int | None = None,
+        severity_overrides: dict[str, str] | None = None,
+        proximity_window: int | None = None,
+        band_cutoffs: tuple[float, float, float] | None = None,
+        density_weighting: bool = False,
+        unified_scale: bool = False,
+        aggregation: str = "max",
+        decode_payloads: bool = False,
+        normalize: bool = True,
+        deleet: bool = False,
+        disabled_rules: list[str] | None = None,
+        allowlist: list[str] | None = None,
+        weight_overrides: dict[str, float] | None = None,
+        multi_match_bonus: float | None = None,
+    ) -> None:
         self.threshold = threshold
+        self.action_overrides = action_overrides
+        self.stats = stats
+        self.scan_last_sentences = scan_last_sentences
+        self.severity_overrides = severity_overrides
+        self.proximity_window = proximity_window
+        self.band_cutoffs = band_cutoffs
+        self.density_weighting = density_weighting
+        self.unified_scale = unified_scale
+        self.aggregation = aggregation
+        self.decode_payloads = decode_payloads
+        self.normalize = normalize
+        self.deleet = deleet
+        self.disabled_rules = disabled_rules
+        self.allowlist = allowlist
+        self.weight_overrides = weight_overrides
+        self.multi_match_bonus = multi_match_bonus
+
+    def _config(self) -> ScanConfig:
+        """The detector's scan settings, so every method scores exactly as
+        :meth:`analyse` would."""
+        return ScanConfig(
+            scan_last_sentences=self.scan_last_sentences,
+            proximity_window=self.proximity_window,
+            density_weighting=self.density_weighting,
+            unified_scale=self.unified_scale,
+            aggregation=self.aggregation,
+            decode_payloads=self.decode_payloads,
+            normalize=self.normalize,
+            deleet=self.deleet,
+            disabled_rules=self.disabled_rules,
+            allowlist=self.allowlist,
+            weight_overrides=self.weight_overrides,
+            multi_match_bonus=self.multi_match_bonus,
+        )
 
     def score(self, text: str) -> float:
         """Return an injection-likelihood score in ``[0.0, 1.0]``."""
-        return injection_score(text)
+        return injection_score(text, config=self._config())
+
+    def score_bp(self, text: str) -> int:
+        """Return the injection score as integer basis points (0--10000)."""
+        return injection_score_bp(text, config=self._config())
 
     def detect(self, text: str, threshold: float | None = None) -> bool:
         """Return ``True`` if the text is classified as a prompt injection."""
         effective = threshold if threshold is not None else self.threshold
         return self.score(text) >= effective
 
-    def analyse(self, text: str, threshold: float | None = None) -> InjectionResult:
-        """Full analysis with score, boolean flag, and matched-rule labels."""
+    def analyse(
+        self, text: str, threshold: float | None = None, return_normalized: bool = False
+    ) -> InjectionResult:
+        """Full analysis with score, boolean flag, matched-rule labels and action hint.
+
+        With *return_normalized*, the result includes the text the rules
+        actually matched against, which helps when investigating evasions.
+        """
         effective = threshold if threshold is not None else self.threshold
-        score, is_injection, matched_rules = injection_analyse(text, effective)
-        return InjectionResult(
-            score=score,
-            is_injection=is_injection,
-            matched_rules=matched_rules,
+        result = injection_analyse_detailed(
+            text,
+            effective,
+            action_overrides=self.action_overrides,
+            stats=self.stats,
+            scan_last_sentences=self.scan_last_sentences,
+            severity_overrides=self.severity_overrides,
+            proximity_window=self.proximity_window,
+            return_normalized=return_normalized,
+            density_weighting=self.density_weighting,
+            unified_scale=self.unified_scale,
+            aggregation=self.aggregation,
+            decode_payloads=self.decode_payloads,
+            normalize=self.normalize,
+            deleet=self.deleet,
+            disabled_rules=self.disabled_rules,
+            allowlist=self.allowlist,
+            weight_overrides=self.weight_overrides,
+            multi_match_bonus=self.multi_match_bonus,
         )
+        return InjectionResult(**result)
+
+    def verdict(self, text: str, threshold: float | None = None) -> InjectionVerdict:
+        """Analyse *text* and return a typed :class:`InjectionVerdict`.
+
+        Each entry of ``verdict.matched_rules`` carries the rule's ``id``,
+        ``label``, ``weight``, ``severity`` (after ``severity_overrides``) and
+        match ``spans``;
+        ``verdict.primary_rule`` is the highest-weighted one and
+        ``verdict.to_dict()`` gives a plain-dict copy for logging.
+        """
+        effective = threshold if threshold is not None else self.threshold
+        return injection_analyse_v2(
+            text,
+            effective,
+            stats=self.stats,
+            scan_last_sentences=self.scan_last_sentences,
+            proximity_window=self.proximity_window,
+            density_weighting=self.density_weighting,
+            unified_scale=self.unified_scale,
+            aggregation=self.aggregation,
+            decode_payloads=self.decode_payloads,
+            normalize=self.normalize,
+            deleet=self.deleet,
+            disabled_rules=self.disabled_rules,
+            allowlist=self.allowlist,
+            weight_overrides=self.weight_overrides,
+            multi_match_bonus=self.multi_match_bonus,
+            severity_overrides=self.severity_overrides,
+        )
+
+    def analyse_with_rules(
+        self,
+        text: str,
+        custom_rules: list[tuple[str, str, float, str]],
+        threshold: float | None = None,
+    ) -> tuple[float, bool, list[str], list[str]]:
+        """Score *text* with extra rules for this call only.
+
+        *custom_rules* are ``(label, regex, weight, explanation)`` tuples
+        checked after the built-in and registered rules; they show up in the
+        returned ``(score, is_injection, matched_rules, matched_rule_ids)``
+        like any other rule, with the ID ``custom:<label>``.  Each distinct
+        rule is compiled once and reused, so pass the same rule list on every
+        call rather than building new regexes per request.  Raises ``ValueError`` for an invalid regex or
+        weight, or a label already in use.  The detector's other settings
+        apply as in :meth:`analyse`.
+        """
+        effective = threshold if threshold is not None else self.threshold
+        return injection_analyse_with_rules(
+            text,
+            effective,
+            custom_rules,
+            stats=self.stats,
+            scan_last_sentences=self.scan_last_sentences,
+            proximity_window=self.proximity_window,
+            density_weighting=self.density_weighting,
+            unified_scale=self.unified_scale,
+            aggregation=self.aggregation,
+            decode_payloads=self.decode_payloads,
+            normalize=self.normalize,
+            deleet=self.deleet,
+            disabled_rules=self.disabled_rules,
+            allowlist=self.allowlist,
+            weight_overrides=self.weight_overrides,
+            multi_match_bonus=self.multi_match_bonus,
+        )
+
+    def classify(self, text: str) -> tuple[float, str, list[str], list[str]]:
+        """Return ``(score, band, matched_rules, matched_rule_ids)``.
+
+        ``band`` is one of ``clean``, ``suspicious``, ``likely`` or
+        ``certain`` -- stable categories for policies that should not
+        depend on raw score thresholds.  With ``unified_scale``, the band
+        cutoffs are read on the unified scale.
+        """
+        return injection_classify(text, cutoffs=self.band_cutoffs, config=self._config())
+
+    def scan_structured(self, text: str) -> tuple[float, str | None, list[str], list[str]]:
+        """Scan JSON input value by value: ``(score, path, matched_rules,
+        matched_rule_ids)``.
+
+        *path* locates the riskiest string value (``"meta.notes[1]"``) and is
+        ``None`` when nothing matched or *text* is not a JSON object/array
+        (which is then scanned as plain text).
+        """
+        return injection_scan_structured(text, config=self._config())
+
+    def segments(self, text: str, threshold: float | None = None) -> list[dict]:
+        """Score each sentence of *text* on its own.
+
+        Returns one dict per sentence with ``sentence``, ``start`` and
+        ``end`` (byte offsets into *text*), ``score``, ``is_injection``,
+        ``matched_rules``, ``matched_rule_ids`` and ``severity`` (after
+        ``severity_overrides``), so a single malicious sentence in a long
+        document can be pinpointed and removed.
+        """
+        effective = threshold if threshold is not None else self.threshold
+        return injection_segments(
+            text,
+            effective,
+            config=self._config(),
+            severity_overrides=self.severity_overrides,
+        )
+
+    @staticmethod
+    def register_rule(
+        label: str,
+        pattern: str,
+        weight: float,
+        explanation: str = "",
+        action: str = "flag_for_review",
+        id: str | None = None,
+    ) -> None:
+        """Add a custom detection rule for every detector in the process.
+
+        Undo with :func:`llm_guardrails.reset_custom_rules` or a
+        :func:`llm_guardrails.snapshot_custom_state` /
+        :func:`llm_guardrails.restore_custom_state` pair.
+        """
+        injection_register_rule(
+            label, pattern, weight, explanation=explanation, action=action, id=id
+        )
+
+    @staticmethod
+    def has_whitespace_padding(text: str) -> bool:
+        """Return ``True`` if *text* hides content behind abnormal whitespace padding."""
+        return contains_whitespace_padding(text)
 
     def list_rules(self) -> list[dict[str, str | float]]:
         """Return a human-readable list of all active detection rules."""
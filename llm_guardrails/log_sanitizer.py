@@ -0,0 +1,23 @@
+"""Sanitise untrusted text before writing it to logs.
+
+Thin Python wrapper around the Rust ``_core`` implementation, which strips
+control/invisible characters, redacts PII and truncates in a single call.
+"""
+
+from __future__ import annotations
+
+from llm_guardrails._core import sanitize_for_log as _sanitize_for_log
+
+__all__ = ["sanitize_for_log"]
+
+
+def sanitize_for_log(text: str, max_len: int = 1000) -> tuple[str, list[str]]:
+    """Return ``(safe_text, summary)`` for *text*.
+
+    Control and invisible characters are stripped first (so they cannot hide
+    PII from the redactor), PII is replaced with placeholders, and the result
+    is truncated to at most *max_len* characters.  *summary* lists each step
+    that changed the text.  No mapping is returned: logged text is not meant
+    to be restored.
+    """
+    return _sanitize_for_log(text, max_len)
@@ -10,8 +10,19 @@ from typing import Any
 
 from pydantic import BaseModel
 
+from llm_guardrails._core import StreamingOutputValidator
+from llm_guardrails._core import output_hedging_matches as _output_hedging_matches
 from llm_guardrails._core import output_validate as _output_validate
 
+__all__ = [
+    "HedgingMatch",
+    "OutputValidator",
+    "StreamingOutputValidator",
+    "ValidationIssue",
+    "ValidationResult",
+    "ValidationRules",
+]
+
 
 class ValidationIssue(BaseModel):
     """A single validation problem."""
@@ -19,6 +30,19 @@ class ValidationIssue(BaseModel):
     rule: str
     message: str
     severity: str = "error"
+    path: str | None = None
+    """Dotted location in the JSON output (JSON-schema issues only)."""
+    schema_path: str | None = None
+    """Dotted location of the failing schema keyword (``include_schema_path``)."""
+
+
+class HedgingMatch(BaseModel):
+    """A hedging phrase that contributed to the hallucination score."""
+
+    phrase: str
+    start: int
+    end: int
+    """Byte offsets of the phrase in the validated text."""
 
 
 class ValidationResult(BaseModel):
@@ -27,17 +51,72 @@ class ValidationResult(BaseModel):
     is_valid: bool
     issues: list[ValidationIssue] = []
     hallucination_score: float = 0.0
+    hedging_matches: list[HedgingMatch] | None = None
+    """Phrases behind ``hallucination_score``; ``None`` when the check is disabled."""
+    repaired_json: str | None = None
+    """Repaired JSON when ``attempt_repair`` fixed the output; each fix is
+    also reported as a ``json_repair`` issue."""
 
 
 class ValidationRules(BaseModel):
     """Configuration object describing which checks to run."""
 
     json_schema: dict[str, Any] | None = None
+    include_schema_path: bool = False
+    attempt_repair: bool = False
+    """Repair near-JSON (trailing commas, single quotes, bare keys, Python
+    literals, code fences) before schema validation."""
+    check_nonempty: bool = True
+    """Fail empty or whitespace-only output with an ``empty_output`` error."""
     max_length: int | None = None
     check_hallucination: bool = True
     hallucination_threshold: float = 0.6
+    unified_scale: bool = False
+    """Report the hallucination score (and read ``hallucination_threshold``)
+    on the scale shared with the injection and bias scores, where ``0.5`` is
+    the default threshold."""
+    per_sentence_hedging: bool = False
+    """Report each heavily hedged sentence as its own ``hedging`` warning."""
+    sentence_hedging_threshold: float = 0.2
+    """Hedging phrases per word at which a sentence is reported."""
     required_keywords: list[str] | None = None
     blocked_keywords: list[str] | None = None
+    """Keywords are matched caselessly using Unicode case folding, so
+    ``"STRASSE"`` matches ``"straße"`` and ``"İstanbul"`` matches ``"istanbul"``."""
+    blocked_keyword_weights: dict[str, float] | None = None
+    """Terms that add their weight to a risk score instead of failing outright;
+    the matches are reported as one ``blocked_keyword_score`` warning."""
+    blocked_keyword_threshold: float = 1.0
+    """Score at which ``blocked_keyword_score`` becomes an error."""
+    expected_language: str | None = None
+    """ISO 639-1 code (e.g. ``"en"``); a ``language_mismatch`` warning is
+    emitted when the output appears to be in a different language."""
+    require_citations: bool = False
+    """Fail answers of more than a few words that contain no citation."""
+    citation_pattern: str | None = None
+    """Regex a citation must match; defaults to a ``[n]`` marker or a URL."""
+    check_placeholder_leak: bool = False
+    """Warn when redaction placeholders such as ``<<SSN_1>>`` appear in the output."""
+    placeholder_delimiters: tuple[str, str] | None = None
+    """Placeholder delimiters for that check; defaults to ``("<<", ">>")``."""
+    placeholder_template: str | None = None
+    """The ``placeholder_template`` the output was redacted with (e.g.
+    ``"[REDACTED:{label}:{n}]"``), so the check looks for those placeholders
+    instead; cannot be combined with ``placeholder_delimiters``."""
+    check_encoded_blobs: bool = False
+    """Flag long base64/hex runs in the output; blobs that decode to text
+    containing PII are reported as errors."""
+    expect_plain_text: bool = False
+    """Fail output that is JSON, opens with a code fence or contains HTML tags
+    when prose was asked for."""
+    field_constraints: dict[str, list[str]] | None = None
+    """Map of dotted JSON field paths (``status``, ``user.role``) to their
+    allowed values; a missing field or other value is a ``field_constraint``
+    error."""
+    range_constraints: str | None = None
+    """Compact numeric range checks such as ``"age:int[0,120], score:float[0,1]"``
+    (inclusive bounds); each violation is a ``field_constraint`` error and a
+    malformed string raises ``ValueError``."""
 
 
 class OutputValidator:
@@ -50,27 +129,47 @@ class OutputValidator:
 
         schema_str = json.dumps(rules.json_schema) if rules.json_schema else None
 
-        is_valid, issues_raw, hallucination_score = _output_validate(
+        is_valid, issues_raw, hallucination_score, repaired_json = _output_validate(
             text,
             json_schema=schema_str,
+            check_nonempty=rules.check_nonempty,
             max_length=rules.max_length,
             check_hallucination=rules.check_hallucination,
             hallucination_threshold=rules.hallucination_threshold,
+            unified_scale=rules.unified_scale,
+            per_sentence_hedging=rules.per_sentence_hedging,
+            sentence_hedging_threshold=rules.sentence_hedging_threshold,
             required_keywords=rules.required_keywords,
             blocked_keywords=rules.blocked_keywords,
+            expected_language=rules.expected_language,
+            include_schema_path=rules.include_schema_path,
+            require_citations=rules.require_citations,
+            citation_pattern=rules.citation_pattern,
+            attempt_repair=rules.attempt_repair,
+            check_placeholder_leak=rules.check_placeholder_leak,
+            placeholder_delimiters=rules.placeholder_delimiters,
+            placeholder_template=rules.placeholder_template,
+            check_encoded_blobs=rules.check_encoded_blobs,
+            field_constraints=rules.field_constraints,
+            range_constraints=rules.range_constraints,
+            blocked_keyword_weights=rules.blocked_keyword_weights,
+            blocked_keyword_threshold=rules.blocked_keyword_threshold,
+            expect_plain_text=rules.expect_plain_text,
         )
 
-        issues = [
-            ValidationIssue(
-                rule=i["rule"],
-                message=i["message"],
-                severity=i["severity"],
-            )
-            for i in issues_raw
-        ]
+        issues = [ValidationIssue(**i) for i in issues_raw]
+
+        hedging_matches = None
+        if rules.check_hallucination:
+            hedging_matches = [
+                HedgingMatch(phrase=phrase, start=start, end=end)
+                for phrase, start, end in _output_hedging_matches(text)
+            ]
 
         return ValidationResult(
             is_valid=is_valid,
             issues=issues,
             hallucination_score=hallucination_score,
+            hedging_matches=hedging_matches,
+            repaired_json=repaired_json,
         )
@@ -6,7 +6,35 @@ high-performance regex-based PII detection.
 
 from __future__ import annotations
 
-from llm_guardrails._core import pii_redact, pii_restore
+from collections.abc import Callable
+
+from llm_guardrails._core import (
+    RedactConfig,
+    RuleStats,
+    StreamingRedactor,
+    pii_categories,
+    pii_detect,
+    pii_first_match,
+    pii_list_categories,
+    pii_protect,
+    pii_redact,
+    pii_redact_batch,
+    pii_redact_dual,
+    pii_redact_manifest,
+    pii_redact_plan,
+    pii_redact_positions,
+    pii_redact_report,
+    pii_redact_many,
+    pii_redact_ordered,
+    pii_register_pattern,
+    pii_restore,
+    pii_restore_fuzzy,
+    pii_restore_then_rescan,
+    pii_title_case_regions,
+)
+
+
+__all__ = ["PIIRedactor", "StreamingRedactor"]
 
 
 class PIIRedactor:
@@ -24,8 +52,128 @@ class PIIRedactor:
     'Call me at <<PHONE_1>>'
     >>> redactor.restore(redacted, mapping)
     'Call me at 555-123-4567'
+
+    Parameters
+    ----------
+    validate_cards : bool
+        Only redact credit-card candidates of a card length (13, 15, 16 or
+        19 digits) that pass the Luhn checksum; other digit runs are left to
+        the remaining patterns.  On by default.
+    card_prefixes : list[str] | None
+        Card-number prefixes (BIN ranges) that are always redacted, even
+        when they fail validation -- e.g. internal test-card conventions.
+    stats : RuleStats | None
+        Optional collector that counts redactions per label across calls.
+    require_separators : bool | None
+        Precision/recall knob for SSNs and card numbers.  ``True`` only
+        redacts dashed SSNs and separated card numbers, ``False`` also
+        accepts bare digit runs for both, and ``None`` keeps the default
+        (dashed SSNs, cards with or without separators).
+    record_ids : bool
+        Redact identifiers that follow a label such as ``Account #``,
+        ``Ref:`` or ``ID`` as ``RECORD_ID``.  Bare numbers are left alone.
+    record_id_anchors : list[str] | None
+        Labels that mark a record identifier, replacing the built-in list.
+        Supplying anchors implies ``record_ids``.
+    date_locale : str | None
+        ``"us"`` (month first) or ``"eu"`` (day first).  Date-of-birth
+        candidates that are not a real date in that order, such as
+        ``40/50/60``, are left alone.
+    max_redactions : int | None
+        Cap on the number of placeholders per call.  Past the cap, new
+        values are masked as a bare ``<<LABEL>>`` that cannot be restored;
+        :meth:`redact_capped` reports whether that happened.
+    preserve_message_ids : bool
+        Keep emails wrapped in angle brackets (Message-IDs such as
+        ``<CAB123@mail.gmail.com>``) so reply threading survives, while
+        ordinary addresses are still redacted.
+    name_titles : list[str] | None
+        Titles that are redacted together with the following name, so
+        ``Dr. Jane Doe`` becomes one ``NAME`` placeholder.  Defaults to
+        ``Mr``, ``Mrs``, ``Ms``, ``Dr`` and ``Prof``; ``[]`` disables it.
+    categories : list[str] | None
+        Only redact these PII labels (see :meth:`list_categories`), e.g.
+        ``["EMAIL", "PHONE"]`` to leave names alone.  ``None`` redacts every
+        category; an unknown label raises ``ValueError``.
+    deterministic : bool
+        Derive each placeholder from a short hash of its value, e.g.
+        ``<<EMAIL_3F2A9C01>>``, so every occurrence of a value (in any call)
+        shares one placeholder and the mapping has one entry per value.
+    custom_patterns : list[tuple[str, str]] | None
+        Extra ``(label, regex)`` pairs applied alongside the built-in
+        patterns, e.g. ``[("EMP_ID", r"EMP-\d{6}")]`` gives
+        ``<<EMP_ID_1>>``.  They lose to every built-in label on overlap.  A
+        bad label or regex raises ``ValueError``; compiled patterns are
+        cached, so reusing one redactor in a loop is cheap.
+    mask_mode : str
+        ``"placeholder"`` (the default) or ``"mask"``.  In mask mode
+        :meth:`redact` keeps the format and last four digits of a value,
+        e.g. ``************1111`` or ``***-**-6789``, and an email's domain
+        (``****@example.com``).  Masking is one-way: the mapping is empty.
+    name_allowlist : list[str] | None
+        Words or phrases that are never part of a ``NAME``, on top of the
+        built-in list of common non-name words (``New York``, ``Monday
+        Morning``, ``The President``), e.g. product or team names.
+    verify_eth_checksum : bool
+        Only redact Ethereum addresses (``0x`` + 40 hex digits) whose
+        mixed-case EIP-55 checksum is valid, leaving hashes and hex dumps
+        alone.  Bitcoin addresses are always redacted as ``BTC_ADDRESS``.
+    secret_entropy_threshold : float
+        Minimum Shannon entropy, in bits per character, for a token of 20+
+        characters mixing upper/lowercase, digits and symbols to be redacted
+        as ``SECRET``.  AWS access key IDs (``AKIA...``) and GitHub tokens
+        (``ghp_...``) are redacted regardless.  Defaults to ``3.5``.
+    placeholder_template : str | None
+        Format of the placeholders :meth:`redact` produces, with ``{label}``
+        and ``{n}`` fields, e.g. ``"[REDACTED:{label}:{n}]"`` when a
+        downstream templating engine treats ``<<`` specially.  Defaults to
+        ``<<{label}_{n}>>``.  Each field must appear once, with a
+        non-alphanumeric delimiter on both sides of ``{n}`` (so ``[EMAIL:1]``
+        is never part of ``[EMAIL:11]``), else ``ValueError`` is raised.
+        Every method, including :meth:`restore` and :meth:`restore_fuzzy`,
+        works unchanged.
     """
 
+    def __init__(
+        self,
+        validate_cards: bool = True,
+        card_prefixes: list[str] | None = None,
+        stats: RuleStats | None = None,
+        require_separators: bool | None = None,
+        record_ids: bool = False,
+        record_id_anchors: list[str] | None = None,
+        date_locale: str | None = None,
+        max_redactions: int | None = None,
+        preserve_message_ids: bool = False,
+        name_titles: list[str] | None = None,
+        categories: list[str] | None = None,
+        deterministic: bool = False,
+        custom_patterns: list[tuple[str, str]] | None = None,
+        mask_mode: str = "placeholder",
+        name_allowlist: list[str] | None = None,
+        verify_eth_checksum: bool = False,
+        secret_entropy_threshold: float = 3.5,
+        placeholder_template: str | None = None,
+    ) -> None:
+        self.validate_cards = validate_cards
+        self.card_prefixes = card_prefixes
+        self.stats = stats
+        self.require_separators = require_separators
+        self.record_ids = record_ids
+        self.record_id_anchors = record_id_anchors
+        self.date_locale = date_locale
+        self.max_redactions = max_redactions
+        self.preserve_message_ids = preserve_message_ids
+        self.name_titles = name_titles
+        self.categories = categories
+        self.deterministic = deterministic
+        self.custom_patterns = custom_patterns
+        self.mask_mode = mask_mode
+        self.name_allowlist = name_allowlist
+        self.verify_eth_checksum = verify_eth_checksum
+        self.secret_entropy_threshold = secret_entropy_threshold
+        self.placeholder_template = placeholder_template
+
     def redact(self, text: str) -> tuple[str, dict[str, str]]:
         """Replace PII tokens with placeholders.
 
@@ -38,9 +186,221 @@ class PIIRedactor:
             ``{placeholder: original_value}`` -- pass this to ``restore``
             to recover the original text.
         """
-        return pii_redact(text)
+        redacted, mapping, _ = self.redact_capped(text)
+        return redacted, mapping
+
+    def redact_capped(self, text: str) -> tuple[str, dict[str, str], bool]:
+        """Like :meth:`redact`, plus whether ``max_redactions`` was reached
+        and some values were masked without a placeholder."""
+        return pii_redact(
+            text,
+            validate_cards=self.validate_cards,
+            card_prefixes=self.card_prefixes,
+            stats=self.stats,
+            require_separators=self.require_separators,
+            record_ids=self.record_ids,
+            record_id_anchors=self.record_id_anchors,
+            date_locale=self.date_locale,
+            preserve_message_ids=self.preserve_message_ids,
+            name_titles=self.name_titles,
+            max_redactions=self.max_redactions,
+            categories=self.categories,
+            deterministic=self.deterministic,
+            custom_patterns=self.custom_patterns,
+            mask_mode=self.mask_mode,
+            name_allowlist=self.name_allowlist,
+            verify_eth_checksum=self.verify_eth_checksum,
+            secret_entropy_threshold=self.secret_entropy_threshold,
+            placeholder_template=self.placeholder_template,
+        )
+
+    def _config(self) -> RedactConfig:
+        """The redactor's settings, so every method redacts exactly what
+        :meth:`redact` would."""
+        return RedactConfig(
+            validate_cards=self.validate_cards,
+            card_prefixes=self.card_prefixes,
+            require_separators=self.require_separators,
+            record_ids=self.record_ids,
+            record_id_anchors=self.record_id_anchors,
+            date_locale=self.date_locale,
+            max_redactions=self.max_redactions,
+            preserve_message_ids=self.preserve_message_ids,
+            name_titles=self.name_titles,
+            categories=self.categories,
+            deterministic=self.deterministic,
+            custom_patterns=self.custom_patterns,
+            mask_mode=self.mask_mode,
+            name_allowlist=self.name_allowlist,
+            verify_eth_checksum=self.verify_eth_checksum,
+            secret_entropy_threshold=self.secret_entropy_threshold,
+            placeholder_template=self.placeholder_template,
+        )
+
+    def redact_many(self, texts: list[str]) -> tuple[list[str], dict[str, str]]:
+        """Redact several texts (e.g. a conversation) with one shared mapping.
+
+        The same value gets the same placeholder in every text, and a single
+        mapping restores any of them.
+        """
+        return pii_redact_many(texts, config=self._config(), stats=self.stats)
+
+    def redact_batch(self, texts: list[str]) -> list[tuple[str, dict[str, str]]]:
+        """Redact each text independently in a single call.
+
+        Returns one ``(redacted_text, mapping)`` pair per text, exactly as
+        :meth:`redact` would, with placeholder numbering restarting per text;
+        much faster than a Python loop over large corpora.  Batches of 64
+        texts or more are split across worker threads; see
+        :func:`llm_guardrails.set_batch_threads` (or the
+        ``LLM_GUARDRAILS_THREADS`` environment variable).
+        """
+        return pii_redact_batch(texts, config=self._config(), stats=self.stats)
+
+    def redact_ordered(self, text: str) -> tuple[str, list[tuple[str, str]]]:
+        """Like :meth:`redact`, but return the mapping as an ordered list.
+
+        The ``(placeholder, original)`` pairs follow their left-to-right
+        appearance in the redacted text, which keeps logs and diffs
+        reproducible.
+        """
+        return pii_redact_ordered(text, config=self._config(), stats=self.stats)
+
+    def redact_dual(self, text: str) -> tuple[str, str, dict[str, str]]:
+        """Redact *text* once and return two views of the same matches.
+
+        Returns ``(placeholder_text, preview_text, mapping)``: the
+        placeholder text is suitable for model input, while the preview
+        text partially masks each value (``a***@***.com``, ``***-**-6789``)
+        for human reviewers.
+        """
+        return pii_redact_dual(text, config=self._config(), stats=self.stats)
+
+    def redact_plan(self, text: str) -> tuple[str, list[dict[str, str | int]]]:
+        """Preview what :meth:`redact` would do, without changing *text*.
+
+        Returns *text* unchanged and one entry per planned redaction with its
+        ``placeholder``, ``label``, ``start``/``end`` byte span in *text* and a
+        partially masked ``preview`` of the value (``a***@***.com``).
+        :meth:`redact` with the same settings replaces exactly those spans
+        with exactly those placeholders (or masks, in mask mode).
+        """
+        return pii_redact_plan(text, config=self._config())
+
+    def redact_positions(
+        self, text: str
+    ) -> tuple[str, dict[str, str], list[tuple[str, int, int]]]:
+        """Like :meth:`redact`, plus ``(placeholder, start, end)`` byte spans
+        of each placeholder in the redacted text, in order -- for re-inserting
+        originals by position after a model rewrites the text."""
+        return pii_redact_positions(text, config=self._config())
+
+    def redact_report(self, text: str) -> tuple[str, dict[str, str], dict[str, int]]:
+        """Like :meth:`redact`, plus the number of occurrences redacted per
+        label and in ``total``, e.g. ``{"EMAIL": 2, "SSN": 1, "total": 3}``."""
+        return pii_redact_report(text, config=self._config())
+
+    def redact_manifest(
+        self, text: str, salt: str | None = None
+    ) -> tuple[str, list[dict[str, str | int]]]:
+        """Redact *text* and return a manifest suitable for compliance records.
+
+        Each manifest entry holds the ``placeholder``, its ``label``, the
+        ``start``/``end`` byte span in the redacted text and a ``sha256``
+        digest of the (optionally salted) original value.  No plaintext PII
+        is included.
+        """
+        return pii_redact_manifest(text, salt=salt, config=self._config())
+
+    def detect(self, text: str) -> list[tuple[str, int, int, str]]:
+        """Return every PII match as ``(label, start, end, matched_text)``.
+
+        Nothing is redacted; offsets are byte offsets into *text*, e.g. for
+        highlighting matches in a UI.  Where patterns overlap, the one
+        :meth:`redact` applies first keeps the span.
+        """
+        return pii_detect(text, config=self._config())
+
+    def first_match(
+        self, text: str, labels: list[str] | None = None
+    ) -> tuple[str, int, int] | None:
+        """Return the earliest PII match as ``(label, start, end)``, or ``None``.
+
+        Stops scanning as soon as a match is found, so this is much cheaper
+        than :meth:`redact` for a yes/no gate.  *labels* restricts the search
+        to those PII labels.  Offsets are byte offsets.
+        """
+        return pii_first_match(text, labels, config=self._config())
+
+    def categories_present(self, text: str) -> list[str]:
+        """Return the distinct PII labels found in *text*, e.g.
+        ``["SSN", "EMAIL"]``, without redacting -- e.g. to route a message.
+
+        Each pattern stops at its first match, so this is cheaper than
+        :meth:`detect`, but overlapping matches are not resolved.
+        """
+        return pii_categories(text, config=self._config())
+
+    @staticmethod
+    def title_case_regions(text: str) -> list[tuple[int, int]]:
+        """Return the byte spans of *text* where NAME detection is suppressed
+        because nearly every word is capitalised (Title Case or ALL CAPS).
+
+        A non-empty result is worth flagging: such documents would otherwise
+        have most of their words redacted as names.
+        """
+        return pii_title_case_regions(text)
+
+    @staticmethod
+    def list_categories() -> list[str]:
+        """Return the PII labels that *categories* accepts, including any
+        added with :meth:`register_pattern`."""
+        return pii_list_categories()
+
+    @staticmethod
+    def register_pattern(label: str, pattern: str) -> None:
+        """Add a custom PII pattern, redacted as ``<<LABEL_N>>``, for every
+        redactor in the process."""
+        pii_register_pattern(label, pattern)
+
+    @staticmethod
+    def restore(text: str, mapping: dict[str, str], fuzzy_restore: bool = False) -> str:
+        """Re-insert original PII values from *mapping* into *text*.
+
+        With *fuzzy_restore*, placeholders the model re-cased or padded with
+        whitespace (``<< email_1 >>``) are restored as well; each such match
+        is logged to the ``llm_guardrails`` logger.
+        """
+        return pii_restore(text, mapping, fuzzy_restore=fuzzy_restore)
 
     @staticmethod
-    def restore(text: str, mapping: dict[str, str]) -> str:
-        """Re-insert original PII values from *mapping* into *text*."""
-        return pii_restore(text, mapping)
+    def restore_fuzzy(text: str, mapping: dict[str, str]) -> str:
+        """Like :meth:`restore` with *fuzzy_restore*: placeholders whose
+        surrounding whitespace or case the model changed
+        (``<< EMAIL_1 >>``) are still restored."""
+        return pii_restore_fuzzy(text, mapping)
+
+    def restore_then_rescan(
+        self, text: str, mapping: dict[str, str]
+    ) -> tuple[str, dict[str, str]]:
+        """Restore *mapping* into *text*, then redact any newly introduced PII
+        with this redactor's settings.
+
+        Known values keep their original placeholders, even where the
+        settings would not detect them again; new values get fresh,
+        non-colliding ones.  Returns ``(safe_text, combined_mapping)``.
+        """
+        return pii_restore_then_rescan(text, mapping, config=self._config())
+
+    def protect(self, text: str, transform: Callable[[str], str]) -> tuple[str, list[str]]:
+        """Redact *text*, run *transform* on the redacted string, then restore.
+
+        Returns
+        -------
+        restored_text : str
+            The transform's output with PII re-inserted.
+        unrestored : list[str]
+            Placeholders the transform dropped or mangled, which could
+            therefore not be restored.
+        """
+        return pii_protect(text, transform, config=self._config())
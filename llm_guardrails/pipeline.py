@@ -65,6 +65,12 @@ class PostProcessResult(BaseModel):
     bias: BiasReport
     """Bias-scoring results."""
 
+    redacted_output: str | None = None
+    """PII-redacted copy of ``final_text`` (only when ``auto_redact_output``)."""
+
+    output_pii_mapping: dict[str, str] = {}
+    """Mapping for ``redacted_output`` (only when ``auto_redact_output``)."""
+
 
 # ---------------------------------------------------------------------------
 # Pipeline
@@ -88,6 +94,9 @@ class GuardrailsPipeline:
         Score threshold for blocking input.
     validation_rules : ValidationRules | None
         Rules passed to the :class:`OutputValidator`.
+    auto_redact_output : bool
+        Also return a PII-redacted copy of the final output, safe for
+        logging, together with its mapping.
     """
 
     pii_enabled: bool = True
@@ -97,6 +106,7 @@ class GuardrailsPipeline:
 
     injection_threshold: float = 0.5
     validation_rules: ValidationRules | None = None
+    auto_redact_output: bool = False
 
     # Guards (lazily initialised for convenience)
     _pii: PIIRedactor = field(default_factory=PIIRedactor, init=False, repr=False)
@@ -157,6 +167,7 @@ class GuardrailsPipeline:
         1. Output validation (if enabled).
         2. Bias scoring (if enabled).
         3. PII restoration (if enabled and mapping is provided).
+        4. Output redaction for logging (if ``auto_redact_output``).
         """
         # 1. Output validation
         if self.output_validation_enabled:
@@ -176,8 +187,16 @@ class GuardrailsPipeline:
         else:
             final_text = text
 
+        # 4. Output redaction -- covers both restored and model-invented PII
+        if self.auto_redact_output:
+            redacted_output, output_pii_mapping = self._pii.redact(final_text)
+        else:
+            redacted_output, output_pii_mapping = None, {}
+
         return PostProcessResult(
             final_text=final_text,
             validation=validation,
             bias=bias,
+            redacted_output=redacted_output,
+            output_pii_mapping=output_pii_mapping,
         )
@@ -0,0 +1,25 @@
+"""Per-module risk verdicts and their agreement for a single text.
+
+Thin Python wrapper around the Rust ``_core`` implementation, which runs the
+injection, PII and bias checks in one call.
+"""
+
+from __future__ import annotations
+
+from llm_guardrails._core import risk_summary as _risk_summary
+
+__all__ = ["risk_summary"]
+
+
+def risk_summary(text: str, config: dict[str, float] | None = None) -> dict[str, object]:
+    """Return each module's verdict on *text* and whether they agree.
+
+    The result has booleans ``injection``, ``pii`` and ``bias`` (``True`` =
+    risky), the supporting ``injection_score``, ``bias_score`` and
+    ``pii_labels``, and ``agreement`` -- ``False`` when the modules
+    disagree, which makes the text a good candidate for human review.
+
+    *config* may set ``injection_threshold`` (default ``0.5``) and
+    ``bias_threshold`` (default ``0.3``).
+    """
+    return _risk_summary(text, config)
@@ -1,7 +1,19 @@
+use std::collections::HashMap;
+
 use once_cell::sync::Lazy;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyList;
 use regex::Regex;
 
+use crate::hidden_unicode::strip_hidden_unicode;
+#[cfg(feature = "python")]
+use crate::output_validator::{issues_to_py, Issue};
+#[cfg(feature = "python")]
+use crate::scoring::to_basis_points;
+use crate::scoring::{round_score, scaled, Detector};
+
 struct StereotypePattern {
     regex: Regex,
     description: &'static str,
@@ -61,59 +73,297 @@ static GENERALISATION_PATTERN: Lazy<Regex> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Occupations checked by the occupational-association mode when the caller
+/// does not supply a list.
+static DEFAULT_OCCUPATIONS: &[&str] = &[
+    "nurse",
+    "engineer",
+    "doctor",
+    "surgeon",
+    "secretary",
+    "receptionist",
+    "programmer",
+    "developer",
+    "pilot",
+    "ceo",
+    "manager",
+    "scientist",
+    "mechanic",
+    "teacher",
+    "librarian",
+    "housekeeper",
+    "nanny",
+    "firefighter",
+    "plumber",
+    "electrician",
+];
+
+static GENDERED_PRONOUNS: &[&str] = &[
+    "he", "him", "his", "himself", "she", "her", "hers", "herself",
+];
+
+/// Negatively loaded adjectives checked by default for loaded framing.
+static DEFAULT_LOADED_ADJECTIVES: &[&str] = &[
+    "lazy",
+    "aggressive",
+    "violent",
+    "dangerous",
+    "criminal",
+    "greedy",
+    "stupid",
+    "dirty",
+    "hysterical",
+    "illegal",
+    "primitive",
+    "savage",
+    "backward",
+    "sneaky",
+    "shrill",
+    "thuggish",
+];
+
+/// Demographic nouns that a loaded adjective may frame.
+static DEMOGRAPHIC_NOUNS: &[&str] = &[
+    "people",
+    "men",
+    "women",
+    "boys",
+    "girls",
+    "immigrants",
+    "migrants",
+    "refugees",
+    "foreigners",
+    "muslims",
+    "christians",
+    "jews",
+    "hindus",
+    "asians",
+    "blacks",
+    "whites",
+    "latinos",
+    "hispanics",
+    "elderly",
+    "seniors",
+    "teenagers",
+];
+
+/// `<adjective> <demographic noun>`, e.g. "lazy immigrants".
+fn loaded_framing_regex<S: AsRef<str>>(adjectives: &[S]) -> Regex {
+    let adjectives: Vec<String> = adjectives
+        .iter()
+        .map(|a| regex::escape(a.as_ref()))
+        .collect();
+    Regex::new(&format!(
+        r"(?i)\b({})\s+({})\b",
+        adjectives.join("|"),
+        DEMOGRAPHIC_NOUNS.join("|")
+    ))
+    .unwrap()
+}
+
+static DEFAULT_LOADED_FRAMING: Lazy<Regex> =
+    Lazy::new(|| loaded_framing_regex(DEFAULT_LOADED_ADJECTIVES));
+
+/// Maximum distance, in words, between an occupation and a gendered pronoun.
+const OCCUPATION_WINDOW: usize = 6;
+
 const STEREOTYPE_WEIGHT: f64 = 0.40;
 const IMBALANCE_WEIGHT: f64 = 0.25;
 const GENERALISATION_WEIGHT: f64 = 0.35;
 const IMBALANCE_THRESHOLD: f64 = 3.0;
+const OCCUPATION_WEIGHT: f64 = 0.25;
 
-fn count_tokens(text: &str, tokens: &[&str]) -> usize {
+/// Per-call scoring settings for [`score`].  `Default` reproduces the
+/// built-in behaviour.
+#[derive(Clone, Debug, Default)]
+pub struct BiasOptions {
+    /// Flag occupations that co-occur with a gendered pronoun.
+    pub occupational_association: bool,
+    /// Occupation terms to check; empty means `DEFAULT_OCCUPATIONS`.
+    pub occupations: Vec<String>,
+    /// Named demographic token groups compared for reference imbalance, in
+    /// flag order; empty means the built-in male/female pair.
+    pub token_groups: Vec<(String, Vec<String>)>,
+    /// Negatively loaded adjectives for the loaded-framing check; empty means
+    /// `DEFAULT_LOADED_ADJECTIVES`.
+    pub loaded_adjectives: Vec<String>,
+    /// Collapse signals whose matched spans overlap into one, keeping the
+    /// most severe (stereotyping over generalisation, earlier patterns first).
+    pub dedupe_overlapping: bool,
+    /// Report the score on the unified scale (see `scoring`).
+    pub unified_scale: bool,
+}
+
+/// Whether `span` overlaps any of `kept`.
+fn overlaps(span: (usize, usize), kept: &[(usize, usize)]) -> bool {
+    kept.iter()
+        .any(|&(start, end)| span.0 < end && start < span.1)
+}
+
+/// Sort caller-supplied token groups by name so flags are deterministic.
+#[cfg_attr(not(feature = "python"), allow(dead_code))]
+fn sorted_groups(groups: Option<HashMap<String, Vec<String>>>) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<_> = groups.unwrap_or_default().into_iter().collect();
+    groups.sort_by(|a, b| a.0.cmp(&b.0));
+    groups
+}
+
+fn count_tokens<S: AsRef<str>>(text: &str, tokens: &[S]) -> usize {
     let lower = text.to_lowercase();
-    tokens.iter().filter(|t| lower.contains(*t)).count()
+    tokens
+        .iter()
+        .filter(|t| lower.contains(&t.as_ref().to_lowercase()))
+        .count()
 }
 
-/// Score text for demographic bias, returning (score, flags).
-#[pyfunction]
-pub fn bias_score(text: &str) -> (f64, Vec<String>) {
+/// Compare reference counts across `groups`, returning the most- and
+/// least-referenced group names and their ratio.  Only groups referenced at
+/// least once take part, and at least two are needed.
+fn reference_imbalance<'a>(
+    text: &str,
+    groups: &[(&'a str, Vec<&str>)],
+) -> Option<(&'a str, &'a str, f64)> {
+    let counts: Vec<(&str, usize)> = groups
+        .iter()
+        .map(|(name, tokens)| (*name, count_tokens(text, tokens)))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    if counts.len() < 2 {
+        return None;
+    }
+    // First group wins ties, keeping the output stable.
+    let mut max = counts[0];
+    let mut min = counts[0];
+    for &entry in &counts[1..] {
+        if entry.1 > max.1 {
+            max = entry;
+        }
+        if entry.1 < min.1 {
+            min = entry;
+        }
+    }
+    Some((max.0, min.0, max.1 as f64 / min.1 as f64))
+}
+
+/// Find (occupation, pronoun) pairs within `OCCUPATION_WINDOW` words of
+/// each other, e.g. "the nurse ... she" or "the engineer fixed it himself".
+fn occupational_associations(text: &str, occupations: &[String]) -> Vec<(String, String)> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let is_occupation = |w: &str| {
+        if occupations.is_empty() {
+            DEFAULT_OCCUPATIONS.contains(&w)
+        } else {
+            occupations.iter().any(|o| o.to_lowercase() == w)
+        }
+    };
+
+    let mut pairs = Vec::new();
+    for (i, word) in words.iter().enumerate() {
+        if !is_occupation(word) {
+            continue;
+        }
+        let lo = i.saturating_sub(OCCUPATION_WINDOW);
+        let hi = (i + OCCUPATION_WINDOW + 1).min(words.len());
+        if let Some(pronoun) = words[lo..hi].iter().find(|w| GENDERED_PRONOUNS.contains(w)) {
+            pairs.push((word.to_string(), pronoun.to_string()));
+        }
+    }
+    pairs
+}
+
+/// Score text for demographic bias under `opts`, returning (score, flags):
+/// the pure-Rust core of `bias_score`.
+pub fn score(text: &str, opts: &BiasOptions) -> (f64, Vec<String>) {
+    let text = &strip_hidden_unicode(text);
     let mut flags: Vec<String> = Vec::new();
     let mut raw_scores: Vec<f64> = Vec::new();
 
+    // Spans of the signals counted so far, for `dedupe_overlapping`.
+    let mut kept: Vec<(usize, usize)> = Vec::new();
+    let mut fresh = |span: (usize, usize)| {
+        let new = !(opts.dedupe_overlapping && overlaps(span, &kept));
+        if new {
+            kept.push(span);
+        }
+        new
+    };
+
     // 1. Stereotyping patterns
     let mut stereotype_hits = 0usize;
     for sp in STEREOTYPE_PATTERNS.iter() {
-        if sp.regex.is_match(text) {
-            flags.push(sp.description.to_string());
-            stereotype_hits += 1;
+        if let Some(m) = sp.regex.find(text) {
+            if fresh((m.start(), m.end())) {
+                flags.push(sp.description.to_string());
+                stereotype_hits += 1;
+            }
         }
     }
+    // Loaded adjectives directly modifying a demographic noun count as
+    // stereotyping too: "those lazy immigrants", "aggressive men".
+    let custom_framing;
+    let framing = if opts.loaded_adjectives.is_empty() {
+        &*DEFAULT_LOADED_FRAMING
+    } else {
+        custom_framing = loaded_framing_regex(&opts.loaded_adjectives);
+        &custom_framing
+    };
+    for caps in framing.captures_iter(text) {
+        let m = caps.get(0).unwrap();
+        if !fresh((m.start(), m.end())) {
+            continue;
+        }
+        flags.push(format!(
+            "Loaded framing: '{}' applied to '{}'",
+            caps[1].to_lowercase(),
+            caps[2].to_lowercase()
+        ));
+        stereotype_hits += 1;
+    }
     if stereotype_hits > 0 {
         let normalised = (stereotype_hits as f64 * 0.5).min(1.0);
         raw_scores.push(normalised * STEREOTYPE_WEIGHT);
     }
 
-    // 2. Gender-reference imbalance
-    let male_count = count_tokens(text, MALE_TOKENS);
-    let female_count = count_tokens(text, FEMALE_TOKENS);
-    if male_count > 0 && female_count > 0 {
-        let max_c = male_count.max(female_count) as f64;
-        let min_c = male_count.min(female_count) as f64;
-        let ratio = max_c / min_c;
+    // 2. Reference imbalance across demographic token groups
+    let custom = !opts.token_groups.is_empty();
+    let groups: Vec<(&str, Vec<&str>)> = if custom {
+        opts.token_groups
+            .iter()
+            .map(|(name, tokens)| (name.as_str(), tokens.iter().map(String::as_str).collect()))
+            .collect()
+    } else {
+        vec![
+            ("male", MALE_TOKENS.to_vec()),
+            ("female", FEMALE_TOKENS.to_vec()),
+        ]
+    };
+    if let Some((dominant, other, ratio)) = reference_imbalance(text, &groups) {
         if ratio >= IMBALANCE_THRESHOLD {
-            let dominant = if male_count > female_count {
-                "male"
+            if custom {
+                flags.push(format!(
+                    "Reference imbalance: '{}' references outnumber '{}' by {:.1}x",
+                    dominant, other, ratio
+                ));
             } else {
-                "female"
-            };
-            flags.push(format!(
-                "Gender-reference imbalance: {} references outnumber the other by {:.1}x",
-                dominant, ratio
-            ));
+                flags.push(format!(
+                    "Gender-reference imbalance: {} references outnumber the other by {:.1}x",
+                    dominant, ratio
+                ));
+            }
             let score = ((ratio - IMBALANCE_THRESHOLD) / 5.0 + 0.3).min(1.0);
             raw_scores.push(score * IMBALANCE_WEIGHT);
         }
     }
 
     // 3. Absolute generalisations
-    let gen_matches: Vec<_> = GENERALISATION_PATTERN.find_iter(text).collect();
+    let gen_matches: Vec<_> = GENERALISATION_PATTERN
+        .find_iter(text)
+        .filter(|m| fresh((m.start(), m.end())))
+        .collect();
     if !gen_matches.is_empty() {
         let count = gen_matches.len();
         flags.push(format!(
@@ -124,32 +374,194 @@ pub fn bias_score(text: &str) -> (f64, Vec<String>) {
         raw_scores.push(score * GENERALISATION_WEIGHT);
     }
 
+    // 4. Occupational-gender associations (opt-in)
+    if opts.occupational_association {
+        let pairs = occupational_associations(text, &opts.occupations);
+        for (occupation, pronoun) in &pairs {
+            flags.push(format!(
+                "Occupational-gender association: '{}' paired with '{}'",
+                occupation, pronoun
+            ));
+        }
+        if !pairs.is_empty() {
+            let score = (pairs.len() as f64 * 0.5).min(1.0);
+            raw_scores.push(score * OCCUPATION_WEIGHT);
+        }
+    }
+
     let total: f64 = if raw_scores.is_empty() {
         0.0
     } else {
         raw_scores.iter().sum::<f64>().min(1.0)
     };
 
-    // Round to 4 decimal places for consistency with the Python version.
-    let total = (total * 10_000.0).round() / 10_000.0;
+    // Round to the configured precision (4 decimal places by default).
+    let total = scaled(round_score(total), Detector::Bias, opts.unified_scale);
 
     (total, flags)
 }
 
+/// Score text for demographic bias, returning (score, flags, normalized_text).
+///
+/// Zero-width and bidi-control characters are stripped before scanning;
+/// `normalized_text` is that scanned text with `return_normalized`, else
+/// `None`.
+///
+/// With `occupational_association`, occupation terms (from `occupations`, or
+/// a built-in list) appearing within a few words of a gendered pronoun are
+/// flagged as implicit occupational-gender bias.
+///
+/// `token_groups` maps group names to demographic terms; reference imbalance
+/// is then measured between the most- and least-referenced groups instead of
+/// the built-in male/female lists.
+///
+/// Negatively loaded adjectives directly modifying a demographic noun
+/// ("lazy immigrants") add to the stereotype component with a
+/// `Loaded framing` flag; `loaded_adjectives` replaces the built-in lexicon.
+///
+/// With `dedupe_overlapping`, signals whose matched text overlaps (e.g. "All
+/// women are" read as both a stereotype and a generalisation) are flagged
+/// and scored once, as the most severe of them.
+///
+/// With `unified_scale`, the score is mapped onto the scale shared with the
+/// injection and hallucination scores, where 0.5 is the default threshold.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, occupational_association=false, occupations=None, token_groups=None, loaded_adjectives=None, return_normalized=false, dedupe_overlapping=false, unified_scale=false))]
+pub fn bias_score(
+    text: &str,
+    occupational_association: bool,
+    occupations: Option<Vec<String>>,
+    token_groups: Option<HashMap<String, Vec<String>>>,
+    loaded_adjectives: Option<Vec<String>>,
+    return_normalized: bool,
+    dedupe_overlapping: bool,
+    unified_scale: bool,
+) -> (f64, Vec<String>, Option<String>) {
+    let opts = BiasOptions {
+        occupational_association,
+        occupations: occupations.unwrap_or_default(),
+        token_groups: sorted_groups(token_groups),
+        loaded_adjectives: loaded_adjectives.unwrap_or_default(),
+        dedupe_overlapping,
+        unified_scale,
+    };
+    let (score, flags) = score(text, &opts);
+    (
+        score,
+        flags,
+        return_normalized.then(|| strip_hidden_unicode(text)),
+    )
+}
+
+/// Like `bias_score`, but the score is returned as integer basis points
+/// (0--10000) for exact storage and comparison.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, occupational_association=false, occupations=None, token_groups=None, loaded_adjectives=None, dedupe_overlapping=false, unified_scale=false))]
+pub fn bias_score_bp(
+    text: &str,
+    occupational_association: bool,
+    occupations: Option<Vec<String>>,
+    token_groups: Option<HashMap<String, Vec<String>>>,
+    loaded_adjectives: Option<Vec<String>>,
+    dedupe_overlapping: bool,
+    unified_scale: bool,
+) -> (u32, Vec<String>) {
+    let opts = BiasOptions {
+        occupational_association,
+        occupations: occupations.unwrap_or_default(),
+        token_groups: sorted_groups(token_groups),
+        loaded_adjectives: loaded_adjectives.unwrap_or_default(),
+        dedupe_overlapping,
+        unified_scale,
+    };
+    let (score, flags) = score(text, &opts);
+    (to_basis_points(score), flags)
+}
+
+/// Turn a bias scan into validation issues: every flag becomes a
+/// `bias_signal` warning, and a score at or above `threshold` adds a `bias`
+/// error.
+#[cfg(feature = "python")]
+fn bias_issues(text: &str, threshold: f64, opts: &BiasOptions) -> Vec<Issue> {
+    let (score, flags) = score(text, opts);
+    let mut issues: Vec<Issue> = flags
+        .into_iter()
+        .map(|flag| Issue {
+            rule: "bias_signal".into(),
+            message: flag,
+            severity: "warning".into(),
+            ..Default::default()
+        })
+        .collect();
+    if score >= threshold {
+        issues.push(Issue {
+            rule: "bias".into(),
+            message: format!(
+                "Bias score ({:.2}) meets or exceeds threshold ({:.2})",
+                score, threshold
+            ),
+            severity: "error".into(),
+            ..Default::default()
+        });
+    }
+    issues
+}
+
+/// Gate text on its bias score, returning (is_valid, issues).
+///
+/// `issues` uses the same dict shape as `output_validate` (rule, message,
+/// severity).  The text fails when its score reaches `threshold`; individual
+/// bias signals are reported as warnings either way.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, threshold, occupational_association=false, occupations=None, token_groups=None, loaded_adjectives=None, dedupe_overlapping=false, unified_scale=false))]
+pub fn bias_validate(
+    py: Python<'_>,
+    text: &str,
+    threshold: f64,
+    occupational_association: bool,
+    occupations: Option<Vec<String>>,
+    token_groups: Option<HashMap<String, Vec<String>>>,
+    loaded_adjectives: Option<Vec<String>>,
+    dedupe_overlapping: bool,
+    unified_scale: bool,
+) -> PyResult<(bool, Py<PyList>)> {
+    let opts = BiasOptions {
+        occupational_association,
+        occupations: occupations.unwrap_or_default(),
+        token_groups: sorted_groups(token_groups),
+        loaded_adjectives: loaded_adjectives.unwrap_or_default(),
+        dedupe_overlapping,
+        unified_scale,
+    };
+    let issues = bias_issues(text, threshold, &opts);
+    let is_valid = !issues.iter().any(|i| i.severity == "error");
+    Ok((is_valid, issues_to_py(py, &issues)?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn score_default(text: &str) -> (f64, Vec<String>) {
+        score(text, &BiasOptions::default())
+    }
+
     #[test]
     fn test_neutral_text() {
-        let (score, flags) = bias_score("The weather today is sunny with a high of 75 degrees.");
+        let (score, flags) = score_default("The weather today is sunny with a high of 75 degrees.");
         assert_eq!(score, 0.0);
         assert!(flags.is_empty());
     }
 
     #[test]
     fn test_gender_stereotype() {
-        let (score, flags) = bias_score("Women are naturally bad at mathematics.");
+        let (score, flags) = score_default("Women are naturally bad at mathematics.");
         assert!(score > 0.0);
         assert!(flags
             .iter()
@@ -158,8 +570,141 @@ mod tests {
 
     #[test]
     fn test_absolute_generalisation() {
-        let (score, flags) = bias_score("All women are emotional.");
+        let (score, flags) = score_default("All women are emotional.");
         assert!(score > 0.0);
         assert!(!flags.is_empty());
     }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_basis_points() {
+        let text = "All women are emotional.";
+        let (score, flags) = score_default(text);
+        let (bp, bp_flags) = bias_score_bp(text, false, None, None, None, false, false);
+        assert_eq!(bp, (score * 10_000.0).round() as u32);
+        assert_eq!(flags, bp_flags);
+    }
+
+    #[test]
+    fn test_occupational_association() {
+        let opts = BiasOptions {
+            occupational_association: true,
+            ..Default::default()
+        };
+        let (score, flags) = score("The engineer fixed it himself.", &opts);
+        assert!(score > 0.0);
+        assert!(flags.iter().any(|f| f.contains("'engineer'")));
+
+        let (score, flags) = super::score("The engineer fixed it themselves.", &opts);
+        assert_eq!(score, 0.0);
+        assert!(flags.is_empty());
+
+        // Off by default.
+        assert!(score_default("The engineer fixed it himself.").1.is_empty());
+    }
+
+    #[test]
+    fn test_custom_token_groups() {
+        let mut groups = HashMap::new();
+        groups.insert(
+            "young".to_string(),
+            vec!["teen".to_string(), "youth".to_string()],
+        );
+        groups.insert(
+            "old".to_string(),
+            vec![
+                "elder".to_string(),
+                "retiree".to_string(),
+                "pensioner".to_string(),
+            ],
+        );
+        let opts = BiasOptions {
+            token_groups: sorted_groups(Some(groups)),
+            ..Default::default()
+        };
+        let text = "The elder, the retiree and the pensioner spoke; one teen listened.";
+        let (score, flags) = score(text, &opts);
+        assert!(score > 0.0);
+        assert!(flags
+            .iter()
+            .any(|f| f == "Reference imbalance: 'old' references outnumber 'young' by 3.0x"));
+
+        // Custom groups replace the built-in gender pair.
+        let text = "He said his father and his brother met him; she waved.";
+        assert!(super::score(text, &opts).1.is_empty());
+    }
+
+    #[test]
+    fn test_loaded_framing() {
+        let (score, flags) = score_default("Those lazy immigrants took the jobs.");
+        assert!(score > 0.0);
+        assert_eq!(
+            flags,
+            vec!["Loaded framing: 'lazy' applied to 'immigrants'"]
+        );
+
+        let (score, flags) = score_default("Hardworking people built this town.");
+        assert_eq!(score, 0.0);
+        assert!(flags.is_empty());
+
+        let opts = BiasOptions {
+            loaded_adjectives: vec!["hardworking".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            super::score("Hardworking people built this town.", &opts)
+                .1
+                .len(),
+            1
+        );
+        assert!(super::score("Those lazy immigrants.", &opts).1.is_empty());
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_bias_validate_gate() {
+        let opts = BiasOptions::default();
+        let issues = bias_issues("All women are emotional.", 0.3, &opts);
+        assert!(issues
+            .iter()
+            .any(|i| i.rule == "bias" && i.severity == "error"));
+        assert!(issues.iter().any(|i| i.rule == "bias_signal"));
+
+        let issues = bias_issues("The meeting starts at noon.", 0.3, &opts);
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_normalized_text_strips_zero_width() {
+        let text = "Those la\u{200B}zy immigrants took the jobs.";
+        let (score, flags) = score_default(text);
+        assert!(score > 0.0);
+        assert_eq!(flags.len(), 1);
+        let normalized = strip_hidden_unicode(text);
+        assert_ne!(normalized, text);
+        assert_eq!(normalized, "Those lazy immigrants took the jobs.");
+    }
+
+    #[test]
+    fn test_dedupe_overlapping() {
+        // "All women are" is both a stereotype and a generalisation marker.
+        let text = "All women are bad at maths.";
+        let (double, double_flags) = score_default(text);
+        assert_eq!(double_flags.len(), 2);
+
+        let opts = BiasOptions {
+            dedupe_overlapping: true,
+            ..Default::default()
+        };
+        let (single, flags) = score(text, &opts);
+        assert_eq!(
+            flags,
+            vec!["Absolute generalisation about a demographic group"]
+        );
+        assert!(single < double);
+
+        // Separate offences are still counted separately.
+        let apart = "All women are bad at maths. Never men from there.";
+        assert_eq!(score(apart, &opts).1.len(), 2);
+    }
 }
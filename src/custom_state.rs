@@ -0,0 +1,81 @@
+//! Isolation for runtime-registered state.
+//!
+//! Custom injection rules and PII patterns are process-wide, so tests and
+//! multi-tenant services snapshot them before registering and restore the
+//! snapshot afterwards.  `RuleStats` collectors are owned by the caller and
+//! are not part of this state.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::injection_detector::{restore_custom_rules, snapshot_custom_rules, CustomRules};
+use crate::pii_redactor::{restore_custom_patterns, snapshot_custom_patterns, CustomPatterns};
+
+/// Opaque handle to the custom rules and patterns registered at one point.
+#[cfg_attr(feature = "python", pyclass(frozen))]
+#[derive(Default)]
+pub struct CustomStateSnapshot {
+    injection_rules: CustomRules,
+    pii_patterns: CustomPatterns,
+}
+
+impl CustomStateSnapshot {
+    fn capture() -> Self {
+        Self {
+            injection_rules: snapshot_custom_rules(),
+            pii_patterns: snapshot_custom_patterns(),
+        }
+    }
+
+    /// Replace the registered custom rules and patterns with this snapshot's,
+    /// dropping anything registered since it was taken.
+    pub fn restore(&self) {
+        restore_custom_rules(&self.injection_rules);
+        restore_custom_patterns(&self.pii_patterns);
+    }
+}
+
+/// Capture the currently registered custom injection rules and PII patterns.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn snapshot_custom_state() -> CustomStateSnapshot {
+    CustomStateSnapshot::capture()
+}
+
+/// Replace the registered custom rules and patterns with `snapshot`'s,
+/// dropping anything registered since it was taken.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn restore_custom_state(snapshot: &Bound<'_, CustomStateSnapshot>) {
+    snapshot.get().restore();
+}
+
+/// Remove every custom injection rule and PII pattern.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn reset_all_custom_state() {
+    CustomStateSnapshot::default().restore();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::injection_detector::{register_rule, score, ScanOptions};
+    use crate::pii_redactor::{redact, register_pattern, RedactOptions};
+
+    #[test]
+    fn test_snapshot_register_restore() {
+        let text = "Activate zx-canary-mode 7 and mail ticket TKT-00042.";
+        let snapshot = CustomStateSnapshot::capture();
+        assert_eq!(score(text, &ScanOptions::default()), 0.0);
+
+        register_rule("canary_mode", r"zx-canary-mode \d", 0.7, "", "reject", None).unwrap();
+        register_pattern("TICKET", r"TKT-\d{5}").unwrap();
+        assert!(register_pattern("TICKET", r"T-\d").is_err());
+        assert_eq!(score(text, &ScanOptions::default()), 0.7);
+        let (redacted, _) = redact(text, &RedactOptions::default());
+        assert!(redacted.contains("<<TICKET_1>>"));
+
+        snapshot.restore();
+        assert_eq!(score(text, &ScanOptions::default()), 0.0);
+        assert_eq!(redact(text, &RedactOptions::default()).0, text);
+    }
+}
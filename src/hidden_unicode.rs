@@ -0,0 +1,189 @@
+//! Detection of invisible and bidirectional-override characters shared by
+//! the safety checks.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Zero-width characters (U+200B–U+200F), bidi embeddings and overrides
+/// (U+202A–U+202E), bidi isolates (U+2066–U+2069) and the BOM (U+FEFF).
+/// Bidi controls can make text render in a different order than it is read
+/// ("trojan source"); zero-width ones hide content or split keywords.
+pub(crate) fn is_hidden_unicode(c: char) -> bool {
+    matches!(
+        c,
+        '\u{200B}'..='\u{200F}' | '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' | '\u{FEFF}'
+    )
+}
+
+/// Each hidden character in `text` as `("U+XXXX", byte offset)`, in order.
+pub(crate) fn hidden_unicode(text: &str) -> Vec<(String, usize)> {
+    text.char_indices()
+        .filter(|(_, c)| is_hidden_unicode(*c))
+        .map(|(i, c)| (format!("U+{:04X}", c as u32), i))
+        .collect()
+}
+
+/// `text` with its hidden characters removed: the form the detectors scan,
+/// so a zero-width space cannot split a keyword out of a rule's reach.
+pub(crate) fn strip_hidden_unicode(text: &str) -> String {
+    text.chars().filter(|c| !is_hidden_unicode(*c)).collect()
+}
+
+/// True for a pictograph or emoji presentation selector, which a zero-width
+/// joiner legitimately follows in family and profession emoji sequences.
+fn is_emoji(c: char) -> bool {
+    matches!(c, '\u{2600}'..='\u{27BF}' | '\u{FE0F}' | '\u{1F000}'..='\u{1FAFF}')
+}
+
+/// How many characters of `text` are invisible: hidden ones, the soft hyphen,
+/// word joiners and invisible operators (U+2060–U+2064), and non-whitespace
+/// control characters.  A zero-width joiner right after an emoji is part of
+/// the emoji and is not counted.
+pub(crate) fn invisible_count(text: &str) -> usize {
+    let mut prev = None;
+    text.chars()
+        .filter(|&c| {
+            let counted = match c {
+                '\u{200D}' => !prev.is_some_and(is_emoji),
+                '\u{00AD}' | '\u{2060}'..='\u{2064}' => true,
+                c => is_hidden_unicode(c) || (c.is_control() && !c.is_whitespace()),
+            };
+            prev = Some(c);
+            counted
+        })
+        .count()
+}
+
+/// The ASCII letter a common confusable renders as, e.g. Cyrillic `і` or
+/// Greek `ο`; fullwidth ASCII (U+FF01–U+FF5E) maps to plain ASCII.  Other
+/// characters are returned unchanged.
+fn fold_homoglyph(c: char) -> char {
+    match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        // Cyrillic
+        'а' => 'a',
+        'с' => 'c',
+        'ԁ' => 'd',
+        'е' => 'e',
+        'һ' => 'h',
+        'і' => 'i',
+        'ј' => 'j',
+        'о' => 'o',
+        'р' => 'p',
+        'ԛ' => 'q',
+        'ѕ' => 's',
+        'ԝ' => 'w',
+        'х' => 'x',
+        'у' => 'y',
+        'А' => 'A',
+        'В' => 'B',
+        'С' => 'C',
+        'Е' => 'E',
+        'Н' => 'H',
+        'І' => 'I',
+        'Ј' => 'J',
+        'К' => 'K',
+        'М' => 'M',
+        'О' => 'O',
+        'Р' => 'P',
+        'Ѕ' => 'S',
+        'Т' => 'T',
+        'Х' => 'X',
+        'У' => 'Y',
+        // Greek
+        'α' => 'a',
+        'ι' => 'i',
+        'κ' => 'k',
+        'ν' => 'v',
+        'ο' => 'o',
+        'ρ' => 'p',
+        'υ' => 'u',
+        'Α' => 'A',
+        'Β' => 'B',
+        'Ε' => 'E',
+        'Ζ' => 'Z',
+        'Η' => 'H',
+        'Ι' => 'I',
+        'Κ' => 'K',
+        'Μ' => 'M',
+        'Ν' => 'N',
+        'Ο' => 'O',
+        'Ρ' => 'P',
+        'Τ' => 'T',
+        'Υ' => 'Y',
+        'Χ' => 'X',
+        // Latin lookalikes
+        'ı' => 'i',
+        'ȷ' => 'j',
+        _ => c,
+    }
+}
+
+/// `text` with hidden characters removed and confusable homoglyphs folded to
+/// ASCII, so `іgnore` (Cyrillic `і`) matches the same rules as `ignore`.
+/// Offsets into the result do not line up with `text`.
+pub(crate) fn normalize_confusables(text: &str) -> String {
+    text.chars()
+        .filter(|c| !is_hidden_unicode(*c))
+        .map(fold_homoglyph)
+        .collect()
+}
+
+/// The byte span in `text` that each byte of `normalize_confusables(text)`
+/// came from, so matches in the normalized text can be mapped back.
+#[cfg(feature = "python")]
+pub(crate) fn confusable_offsets(text: &str) -> Vec<(usize, usize)> {
+    text.char_indices()
+        .filter(|(_, c)| !is_hidden_unicode(*c))
+        .flat_map(|(i, c)| std::iter::repeat_n((i, i + c.len_utf8()), fold_homoglyph(c).len_utf8()))
+        .collect()
+}
+
+/// Return every zero-width or bidi-control character in `text` as
+/// `(code_point, byte_offset)`, e.g. `("U+202E", 12)`.  Empty for clean text.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn contains_hidden_unicode(text: &str) -> Vec<(String, usize)> {
+    hidden_unicode(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rlo_flagged_at_offset() {
+        let text = "invoice_\u{202E}fdp.exe";
+        assert_eq!(hidden_unicode(text), vec![("U+202E".to_string(), 8)]);
+        assert!(hidden_unicode("plain text, naïve café").is_empty());
+    }
+
+    #[test]
+    fn test_strip_hidden_unicode() {
+        assert_eq!(strip_hidden_unicode("ig\u{200B}nore\u{FEFF}"), "ignore");
+    }
+
+    #[test]
+    fn test_normalize_confusables() {
+        assert_eq!(normalize_confusables("\u{0456}gn\u{043E}re"), "ignore");
+        assert_eq!(normalize_confusables("D\u{200C}\u{0391}N"), "DAN");
+        assert_eq!(normalize_confusables("\u{FF29}gnore"), "Ignore");
+        assert_eq!(normalize_confusables("naïve café"), "naïve café");
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_confusable_offsets() {
+        // Cyrillic "і" (2 bytes) folds to "i" (1 byte); the ZWSP is dropped.
+        let text = "\u{0456}g\u{200B}n";
+        assert_eq!(normalize_confusables(text), "ign");
+        assert_eq!(confusable_offsets(text), vec![(0, 2), (2, 3), (6, 7)]);
+    }
+
+    #[test]
+    fn test_invisible_count() {
+        assert_eq!(invisible_count("plain text\n\tnaïve café"), 0);
+        assert_eq!(invisible_count("a\u{202E}\u{200D}\u{2060}\u{0007}b"), 4);
+        // Joiners inside an emoji sequence are not invisible padding.
+        assert_eq!(invisible_count("\u{1F468}\u{200D}\u{1F469}"), 0);
+    }
+}
@@ -1,18 +1,112 @@
+//! Rust core of the LLM Guardrails toolkit.
+//!
+//! With the default `python` feature this crate builds the `_core` Python
+//! extension module.  Without it (`default-features = false`) no Python
+//! runtime is needed and the detectors are used through their pure-Rust
+//! entry points, e.g. [`pii_redactor::redact`], [`injection_detector::analyse`],
+//! [`bias_scorer::score`] and [`output_validator::validate`], with the
+//! settings in [`pii_redactor::RedactOptions`],
+//! [`injection_detector::ScanOptions`], [`bias_scorer::BiasOptions`] and
+//! [`output_validator::ValidateOptions`].  Every `#[pyfunction]` is a thin
+//! wrapper over one of these.
+
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
 
-mod bias_scorer;
-mod injection_detector;
-mod output_validator;
-mod pii_redactor;
+pub mod bias_scorer;
+pub mod custom_state;
+pub mod hidden_unicode;
+pub mod injection_detector;
+pub mod log_sanitizer;
+pub mod output_validator;
+pub mod parallel;
+pub mod pii_redactor;
+pub mod risk_summary;
+pub mod scoring;
+mod sentences;
+pub mod stats;
 
+#[cfg(feature = "python")]
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(pii_redactor::pii_redact, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_manifest, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_ordered, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_dual, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_plan, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_positions, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_report, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_many, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_redact_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_detect, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_first_match, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_categories, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_title_case_regions, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_list_categories, m)?)?;
     m.add_function(wrap_pyfunction!(pii_redactor::pii_restore, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_restore_fuzzy, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_protect, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_restore_then_rescan, m)?)?;
+    m.add_function(wrap_pyfunction!(pii_redactor::pii_register_pattern, m)?)?;
+    m.add_class::<pii_redactor::RedactConfig>()?;
+    m.add_class::<pii_redactor::StreamingRedactor>()?;
     m.add_function(wrap_pyfunction!(injection_detector::injection_score, m)?)?;
+    m.add_function(wrap_pyfunction!(injection_detector::injection_score_bp, m)?)?;
     m.add_function(wrap_pyfunction!(injection_detector::injection_analyse, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::injection_analyse_detailed,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::injection_analyse_v2,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::injection_analyse_with_rules,
+        m
+    )?)?;
+    m.add_class::<injection_detector::InjectionVerdict>()?;
+    m.add_class::<injection_detector::ScanConfig>()?;
+    m.add_class::<injection_detector::MatchedRule>()?;
+    m.add_function(wrap_pyfunction!(injection_detector::injection_classify, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::injection_scan_structured,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(injection_detector::injection_segments, m)?)?;
     m.add_function(wrap_pyfunction!(injection_detector::injection_list_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::injection_register_rule,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(injection_detector::reset_custom_rules, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        injection_detector::contains_whitespace_padding,
+        m
+    )?)?;
     m.add_function(wrap_pyfunction!(bias_scorer::bias_score, m)?)?;
+    m.add_function(wrap_pyfunction!(bias_scorer::bias_score_bp, m)?)?;
+    m.add_function(wrap_pyfunction!(bias_scorer::bias_validate, m)?)?;
     m.add_function(wrap_pyfunction!(output_validator::output_validate, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        output_validator::output_hedging_matches,
+        m
+    )?)?;
+    m.add_class::<output_validator::StreamingOutputValidator>()?;
+    m.add_function(wrap_pyfunction!(log_sanitizer::sanitize_for_log, m)?)?;
+    m.add_function(wrap_pyfunction!(
+        hidden_unicode::contains_hidden_unicode,
+        m
+    )?)?;
+    m.add_function(wrap_pyfunction!(risk_summary::risk_summary, m)?)?;
+    m.add_class::<stats::RuleStats>()?;
+    m.add_class::<custom_state::CustomStateSnapshot>()?;
+    m.add_function(wrap_pyfunction!(custom_state::snapshot_custom_state, m)?)?;
+    m.add_function(wrap_pyfunction!(custom_state::restore_custom_state, m)?)?;
+    m.add_function(wrap_pyfunction!(custom_state::reset_all_custom_state, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::set_score_precision, m)?)?;
+    m.add_function(wrap_pyfunction!(scoring::get_score_precision, m)?)?;
+    m.add_function(wrap_pyfunction!(parallel::set_batch_threads, m)?)?;
+    m.add_function(wrap_pyfunction!(parallel::get_batch_threads, m)?)?;
     Ok(())
 }
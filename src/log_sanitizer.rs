@@ -0,0 +1,120 @@
+//! One-call sanitisation of untrusted text before it is written to logs.
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+use crate::hidden_unicode::is_hidden_unicode;
+use crate::pii_redactor::{placeholder_label, redact, RedactOptions};
+
+/// Appended when the text is cut to fit `max_len`.
+const TRUNCATION_MARKER: char = '…';
+
+/// Zero-width, bidi-override and other invisible format characters that can
+/// hide or reorder content in a log viewer.
+fn is_invisible(c: char) -> bool {
+    is_hidden_unicode(c) || matches!(c, '\u{00AD}' | '\u{180E}' | '\u{2060}'..='\u{2064}')
+}
+
+/// Control characters other than tab and newline, plus invisible characters.
+fn is_stripped(c: char) -> bool {
+    (c.is_control() && c != '\t' && c != '\n') || is_invisible(c)
+}
+
+/// Strip control/invisible characters, redact PII, then truncate to
+/// `max_len` characters.  Returns the safe text and a summary of each step
+/// that changed something.
+fn sanitize(text: &str, max_len: usize) -> (String, Vec<String>) {
+    let mut summary = Vec::new();
+
+    // Strip first so invisible characters cannot split PII and dodge redaction.
+    let stripped: String = text.chars().filter(|c| !is_stripped(*c)).collect();
+    let removed = text.chars().count() - stripped.chars().count();
+    if removed > 0 {
+        summary.push(format!(
+            "stripped {} control/invisible character(s)",
+            removed
+        ));
+    }
+
+    let (redacted, mapping) = redact(&stripped, &RedactOptions::default());
+    if !mapping.is_empty() {
+        let mut labels: Vec<&str> = mapping.keys().map(|p| placeholder_label(p)).collect();
+        labels.sort_unstable();
+        labels.dedup();
+        summary.push(format!(
+            "redacted {} PII value(s) ({})",
+            mapping.len(),
+            labels.join(", ")
+        ));
+    }
+
+    // Truncate last, after redaction, so a cut can never expose a partial value.
+    let length = redacted.chars().count();
+    if length <= max_len {
+        return (redacted, summary);
+    }
+    let mut truncated: String = redacted.chars().take(max_len.saturating_sub(1)).collect();
+    // Do not leave half a placeholder behind.
+    if let Some(open) = truncated.rfind("<<") {
+        if !truncated[open..].contains(">>") {
+            truncated.truncate(open);
+        }
+    }
+    if max_len > 0 {
+        truncated.push(TRUNCATION_MARKER);
+    }
+    summary.push(format!(
+        "truncated from {} to {} characters",
+        length,
+        truncated.chars().count()
+    ));
+    (truncated, summary)
+}
+
+/// Make `text` safe to log in one call: strip control and invisible
+/// characters, redact PII, and truncate to at most `max_len` characters.
+///
+/// Returns `(sanitized_text, summary)` where `summary` describes each step
+/// that changed the text, e.g. `"redacted 2 PII value(s) (EMAIL, PHONE)"`.
+/// No redaction mapping is returned: logged text is not meant to be restored.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn sanitize_for_log(text: &str, max_len: usize) -> (String, Vec<String>) {
+    sanitize(text, max_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_pii_controls_and_length() {
+        let text = "User ali\u{200B}ce@example.com\u{7}\u{1b}[31m called 555-123-4567 about a very long issue";
+        let (safe, summary) = sanitize(text, 40);
+        assert!(!safe.contains("alice@example.com"));
+        assert!(!safe.contains("555-123-4567"));
+        assert!(!safe.chars().any(is_stripped));
+        assert!(safe.chars().count() <= 40);
+        assert!(safe.ends_with(TRUNCATION_MARKER));
+        assert_eq!(
+            summary,
+            vec![
+                "stripped 3 control/invisible character(s)".to_string(),
+                "redacted 2 PII value(s) (EMAIL, PHONE)".to_string(),
+                format!("truncated from 63 to {} characters", safe.chars().count()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_clean_text_untouched() {
+        let (safe, summary) = sanitize("All good.\nNext line.", 100);
+        assert_eq!(safe, "All good.\nNext line.");
+        assert!(summary.is_empty());
+    }
+
+    #[test]
+    fn test_truncation_keeps_placeholders_whole() {
+        let (safe, _) = sanitize("Contact bob@example.com now", 14);
+        assert_eq!(safe, "Contact …");
+    }
+}
@@ -1,8 +1,21 @@
+use std::collections::HashMap;
+
 use once_cell::sync::Lazy;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
+#[cfg(feature = "python")]
 use pyo3::types::{PyDict, PyList};
 use regex::Regex;
 
+use crate::hidden_unicode::is_hidden_unicode;
+use crate::pii_redactor::{
+    check_placeholder_template, placeholder_label, redact, template_pattern, RedactOptions,
+};
+use crate::scoring::{round_score, scaled, Detector};
+use crate::sentences::{sentence_spans, split_sentences};
+
 static HEDGING_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
     let phrases = [
         "I think",
@@ -30,7 +43,8 @@ static HEDGING_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
         .collect()
 });
 
-fn hallucination_score(text: &str) -> f64 {
+/// Hedging-language score in [0.0, 1.0], rounded to the configured precision.
+pub fn hallucination_score(text: &str) -> f64 {
     if text.is_empty() {
         return 0.0;
     }
@@ -38,104 +52,1360 @@ fn hallucination_score(text: &str) -> f64 {
         .iter()
         .filter(|p| p.is_match(text))
         .count();
-    (hits as f64 / 5.0).min(1.0)
+    let contradictions = count_contradictions(text);
+    round_score((hits as f64 / 5.0 + contradictions as f64 * CONTRADICTION_WEIGHT).min(1.0))
+}
+
+/// Score added per adjacent self-contradicting sentence pair.
+const CONTRADICTION_WEIGHT: f64 = 0.2;
+/// Claims shorter than this (in words, excluding negations) are too generic
+/// to compare.
+const CONTRADICTION_MIN_WORDS: usize = 3;
+/// Leading words that introduce a correction and are ignored when comparing.
+const DISCOURSE_MARKERS: &[&str] = &["actually", "however", "but", "no", "wait", "in", "fact"];
+
+/// Reduce a sentence to `(polarity, claim words)`.
+///
+/// Contractions are expanded (`isn't` -> `is not`), leading discourse markers
+/// dropped, and negations (`not`, `never`, and `false` for `true`) removed
+/// from the claim and folded into the polarity flag.
+fn claim_polarity(sentence: &str) -> (bool, Vec<String>) {
+    let lower = sentence.to_lowercase().replace('\u{2019}', "'");
+    let mut words: Vec<String> = Vec::new();
+    for word in lower.split(|c: char| !(c.is_alphanumeric() || c == '\'')) {
+        let word = word.trim_matches('\'');
+        if word.is_empty() {
+            continue;
+        }
+        match word.strip_suffix("n't") {
+            Some(stem) => {
+                let stem = match stem {
+                    "ca" => "can",
+                    "wo" => "will",
+                    "sha" => "shall",
+                    other => other,
+                };
+                words.push(stem.to_string());
+                words.push("not".to_string());
+            }
+            None => words.push(word.to_string()),
+        }
+    }
+    let body = words
+        .iter()
+        .position(|w| !DISCOURSE_MARKERS.contains(&w.as_str()))
+        .unwrap_or(words.len());
+
+    let mut negated = false;
+    let mut claim = Vec::new();
+    for word in words.drain(body..) {
+        match word.as_str() {
+            "not" | "never" => negated = !negated,
+            "false" => {
+                negated = !negated;
+                claim.push("true".to_string());
+            }
+            _ => claim.push(word),
+        }
+    }
+    (negated, claim)
+}
+
+/// Count adjacent sentence pairs that state a claim and then its negation
+/// ("X is true. Actually X is false."), requiring otherwise identical wording
+/// to stay conservative.
+fn count_contradictions(text: &str) -> usize {
+    let claims: Vec<_> = split_sentences(text)
+        .into_iter()
+        .map(claim_polarity)
+        .collect();
+    claims
+        .windows(2)
+        .filter(|pair| {
+            let ((neg_a, claim_a), (neg_b, claim_b)) = (&pair[0], &pair[1]);
+            neg_a != neg_b && claim_a.len() >= CONTRADICTION_MIN_WORDS && claim_a == claim_b
+        })
+        .count()
+}
+
+/// Every hedging phrase occurrence in `text` as `(matched_text, start, end)`
+/// with byte offsets, ordered by position.
+fn hedging_matches(text: &str) -> Vec<(String, usize, usize)> {
+    let mut matches: Vec<_> = HEDGING_PATTERNS
+        .iter()
+        .flat_map(|p| p.find_iter(text))
+        .map(|m| (m.as_str().to_string(), m.start(), m.end()))
+        .collect();
+    matches.sort_by_key(|&(_, start, end)| (start, end));
+    matches
+}
+
+/// One `hedging` warning per sentence whose hedging density (phrases per
+/// word) reaches `threshold`, with the sentence's byte offset.
+fn check_sentence_hedging(text: &str, threshold: f64) -> Vec<Issue> {
+    sentence_spans(text)
+        .into_iter()
+        .filter_map(|(start, end)| {
+            let sentence = &text[start..end];
+            let hedges = hedging_matches(sentence);
+            let words = sentence.split_whitespace().count();
+            let density = hedges.len() as f64 / words.max(1) as f64;
+            if hedges.is_empty() || density < threshold {
+                return None;
+            }
+            let phrases: Vec<&str> = hedges.iter().map(|(p, ..)| p.as_str()).collect();
+            Some(Issue {
+                rule: "hedging".into(),
+                message: format!(
+                    "Sentence at byte {} is heavily hedged ({:.2} per word): {}",
+                    start,
+                    density,
+                    phrases.join(", ")
+                ),
+                severity: "warning".into(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// List the hedging phrases that drive the hallucination score.
+///
+/// Returns `(phrase, start, end)` tuples -- the phrase as written in `text`
+/// and its byte offsets -- in order of appearance.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn output_hedging_matches(text: &str) -> Vec<(String, usize, usize)> {
+    hedging_matches(text)
+}
+
+/// Common function words per ISO 639-1 language code, used by `guess_language`.
+static STOPWORDS: &[(&str, &[&str])] = &[
+    (
+        "en",
+        &[
+            "the", "and", "is", "are", "of", "to", "that", "this", "with", "for", "was", "you",
+            "it",
+        ],
+    ),
+    (
+        "fr",
+        &[
+            "le", "la", "les", "et", "est", "des", "une", "du", "que", "pour", "dans", "pas",
+            "avec", "vous", "nous", "sont",
+        ],
+    ),
+    (
+        "es",
+        &[
+            "el", "los", "las", "y", "es", "del", "una", "que", "para", "con", "por", "como",
+            "pero", "está", "son",
+        ],
+    ),
+    (
+        "de",
+        &[
+            "der", "die", "das", "und", "ist", "nicht", "ein", "eine", "mit", "für", "auf", "sich",
+            "auch", "sind",
+        ],
+    ),
+    (
+        "it",
+        &[
+            "il", "gli", "e", "è", "di", "che", "per", "una", "non", "sono", "della", "con",
+            "anche",
+        ],
+    ),
+    (
+        "pt",
+        &[
+            "o", "os", "e", "é", "do", "da", "que", "para", "com", "uma", "não", "são", "mais",
+        ],
+    ),
+    (
+        "nl",
+        &[
+            "de", "het", "en", "is", "een", "van", "niet", "dat", "met", "voor", "zijn", "ook",
+        ],
+    ),
+];
+
+/// Minimum number of stop-word hits before a Latin-script guess is trusted.
+const MIN_STOPWORD_HITS: usize = 2;
+
+/// Map a character to the language implied by its script, for scripts used by
+/// (essentially) one language.  Latin-script characters return `None`.
+fn script_language(c: char) -> Option<&'static str> {
+    match c {
+        '\u{0400}'..='\u{04FF}' => Some("ru"),
+        '\u{0370}'..='\u{03FF}' => Some("el"),
+        '\u{0590}'..='\u{05FF}' => Some("he"),
+        '\u{0600}'..='\u{06FF}' => Some("ar"),
+        '\u{0900}'..='\u{097F}' => Some("hi"),
+        '\u{3040}'..='\u{30FF}' => Some("ja"),
+        '\u{AC00}'..='\u{D7AF}' => Some("ko"),
+        '\u{4E00}'..='\u{9FFF}' => Some("zh"),
+        _ => None,
+    }
+}
+
+/// Lightweight language guess from script and stop-word frequency.
+///
+/// Returns an ISO 639-1 code, or `None` when the text is too short or
+/// ambiguous to call.
+fn guess_language(text: &str) -> Option<&'static str> {
+    let letters: Vec<char> = text.chars().filter(|c| c.is_alphabetic()).collect();
+    if letters.is_empty() {
+        return None;
+    }
+
+    // Non-Latin scripts are decisive when they make up most of the letters.
+    // Kana wins over Han so that Japanese is not reported as Chinese.
+    let mut script_counts: Vec<(&str, usize)> = Vec::new();
+    for lang in letters.iter().filter_map(|c| script_language(*c)) {
+        match script_counts.iter_mut().find(|(l, _)| *l == lang) {
+            Some((_, n)) => *n += 1,
+            None => script_counts.push((lang, 1)),
+        }
+    }
+    let non_latin: usize = script_counts.iter().map(|(_, n)| n).sum();
+    if non_latin * 2 > letters.len() {
+        if script_counts.iter().any(|(l, _)| *l == "ja") {
+            return Some("ja");
+        }
+        return script_counts
+            .iter()
+            .max_by_key(|(_, n)| *n)
+            .map(|(l, _)| *l);
+    }
+
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let (lang, hits) = STOPWORDS
+        .iter()
+        .map(|(lang, stops)| (*lang, words.iter().filter(|w| stops.contains(w)).count()))
+        .max_by_key(|(_, hits)| *hits)?;
+    (hits >= MIN_STOPWORD_HITS).then_some(lang)
+}
+
+fn check_language(text: &str, expected: &str) -> Vec<Issue> {
+    let expected = expected
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(expected)
+        .to_lowercase();
+    match guess_language(text) {
+        Some(detected) if detected != expected => vec![Issue {
+            rule: "language_mismatch".into(),
+            message: format!(
+                "Output appears to be in '{}' but '{}' was expected",
+                detected, expected
+            ),
+            severity: "warning".into(),
+            ..Default::default()
+        }],
+        _ => Vec::new(),
+    }
+}
+
+/// Bracketed reference markers such as `[1]` or `[12]`, or a URL.
+const DEFAULT_CITATION_PATTERN: &str = r"\[\d+\]|https?://\S+";
+/// Answers shorter than this (in words) are too trivial to need a citation.
+const CITATION_MIN_WORDS: usize = 8;
+
+fn check_citations(text: &str, pattern: &Regex) -> Vec<Issue> {
+    if text.split_whitespace().count() < CITATION_MIN_WORDS || pattern.is_match(text) {
+        return Vec::new();
+    }
+    vec![Issue {
+        rule: "citation".into(),
+        message: "Output contains no citation or source reference".into(),
+        severity: "error".into(),
+        ..Default::default()
+    }]
+}
+
+/// An HTML/XML tag such as `<p>`, `</div>` or `<br/>`.  The leading
+/// character keeps `<<NAME>>` redaction placeholders from matching.
+static HTML_TAG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[^<])</?[A-Za-z][A-Za-z0-9]*(?:\s[^<>]*)?/?>").unwrap());
+
+/// Why `text` is not plain prose: a JSON object or array, a code fence, or
+/// HTML markup.
+fn markup_kind(text: &str) -> Option<&'static str> {
+    let trimmed = text.trim();
+    if trimmed.starts_with("```") {
+        return Some("a code fence");
+    }
+    if trimmed.starts_with(['{', '[']) && serde_json::from_str::<serde_json::Value>(trimmed).is_ok()
+    {
+        return Some("JSON");
+    }
+    HTML_TAG.is_match(text).then_some("HTML tags")
+}
+
+fn check_plain_text(text: &str) -> Vec<Issue> {
+    match markup_kind(text) {
+        Some(kind) => vec![Issue {
+            rule: "format".into(),
+            message: format!("Expected plain text, but the output contains {}", kind),
+            severity: "error".into(),
+            ..Default::default()
+        }],
+        None => Vec::new(),
+    }
+}
+
+/// An `empty_output` error if `text` has nothing but whitespace and
+/// zero-width/bidi-control characters.
+fn check_nonempty_text(text: &str) -> Option<Issue> {
+    text.chars()
+        .all(|c| c.is_whitespace() || is_hidden_unicode(c))
+        .then(|| Issue {
+            rule: "empty_output".into(),
+            message: "Output is empty or whitespace-only".into(),
+            severity: "error".into(),
+            ..Default::default()
+        })
+}
+
+/// Delimiters `pii_redact` wraps placeholders in.
+const DEFAULT_PLACEHOLDER_DELIMITERS: (&str, &str) = ("<<", ">>");
+
+/// The redaction placeholders to look for: those `pii_redact` produces with
+/// `template` (see its `placeholder_template`), or else `<<LABEL_N>>` and the
+/// hashed `<<LABEL_3F2A9C01>>` with the given delimiters.  Giving both is an
+/// error, as is a template `pii_redact` would reject.
+fn placeholder_leak_pattern(
+    delimiters: Option<&(String, String)>,
+    template: Option<&str>,
+) -> Result<Regex, String> {
+    let pattern = match (delimiters, template) {
+        (Some(_), Some(_)) => {
+            return Err(
+                "placeholder_delimiters and placeholder_template cannot both be given".to_string(),
+            )
+        }
+        (_, Some(template)) => {
+            check_placeholder_template(template)?;
+            template_pattern(template)
+        }
+        (delimiters, None) => {
+            let (open, close) = DEFAULT_PLACEHOLDER_DELIMITERS;
+            let (open, close) = delimiters.map_or((open, close), |(o, c)| (o.as_str(), c.as_str()));
+            format!(
+                r"{}[A-Z0-9_]+_[0-9A-F]+{}",
+                regex::escape(open),
+                regex::escape(close)
+            )
+        }
+    };
+    Ok(Regex::new(&pattern).unwrap())
 }
 
-struct Issue {
-    rule: String,
-    message: String,
-    severity: String,
+/// Warn about every distinct redaction placeholder matching `pattern` (see
+/// `placeholder_leak_pattern`) that leaked into the output.
+fn find_placeholder_leaks(text: &str, pattern: &Regex) -> Vec<Issue> {
+    let mut seen: Vec<&str> = Vec::new();
+    for m in pattern.find_iter(text) {
+        if !seen.contains(&m.as_str()) {
+            seen.push(m.as_str());
+        }
+    }
+    seen.into_iter()
+        .map(|placeholder| Issue {
+            rule: "placeholder_leak".into(),
+            message: format!("Redaction placeholder leaked into output: {}", placeholder),
+            severity: "warning".into(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+/// Shortest base64/hex run reported by the encoded-blob check.  Long enough
+/// to skip ordinary words and short IDs; the base64 form of a typical email
+/// address is just over this.
+const ENCODED_BLOB_MIN_LEN: usize = 24;
+
+static ENCODED_BLOB: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"[A-Za-z0-9+/]{{{},}}={{0,2}}",
+        ENCODED_BLOB_MIN_LEN
+    ))
+    .unwrap()
+});
+
+fn base64_value(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some((c - b'A') as u32),
+        b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+        b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
 }
 
-fn check_json(text: &str, schema_str: &str) -> Vec<Issue> {
+/// Decode standard base64, with or without `=` padding.
+pub(crate) fn decode_base64(s: &str) -> Option<Vec<u8>> {
+    let data = s.trim_end_matches('=').as_bytes();
+    if data.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len() * 3 / 4);
+    for chunk in data.chunks(4) {
+        let mut acc = 0u32;
+        for (i, &c) in chunk.iter().enumerate() {
+            acc |= base64_value(c)? << (18 - 6 * i);
+        }
+        let bytes = acc.to_be_bytes();
+        out.extend_from_slice(&bytes[1..chunk.len()]);
+    }
+    Some(out)
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Flag long base64 or hex runs, which often mean the model is smuggling
+/// data out in encoded form.  Blobs that decode to text are re-scanned for
+/// PII; a hit is an error naming the PII labels, otherwise a warning.
+fn find_encoded_blobs(text: &str) -> Vec<Issue> {
     let mut issues = Vec::new();
+    for m in ENCODED_BLOB.find_iter(text) {
+        let blob = m.as_str();
+        // Plain words (even long ones) have no digits and no case mix, and
+        // URL paths are not payloads.
+        let has_digit = blob.bytes().any(|b| b.is_ascii_digit());
+        let mixed_case = blob.bytes().any(|b| b.is_ascii_uppercase())
+            && blob.bytes().any(|b| b.is_ascii_lowercase());
+        let token_start = text[..m.start()]
+            .rfind(char::is_whitespace)
+            .map_or(0, |i| i + 1);
+        let in_url = text[token_start..m.end()].contains("://");
+        if !(has_digit || mixed_case) || in_url {
+            continue;
+        }
+        let (kind, decoded) = if blob.bytes().all(|b| b.is_ascii_hexdigit()) {
+            ("hex", decode_hex(blob))
+        } else {
+            ("base64", decode_base64(blob))
+        };
+        let Some(decoded) = decoded else {
+            continue;
+        };
+        let mut message = format!(
+            "Possible {}-encoded blob at byte {} ({} characters)",
+            kind,
+            m.start(),
+            blob.len()
+        );
+        let mut severity = "warning";
+        if let Ok(decoded) = String::from_utf8(decoded) {
+            let (redacted, mapping) = redact(&decoded, &RedactOptions::default());
+            if !mapping.is_empty() {
+                let mut labels: Vec<&str> = mapping.keys().map(|p| placeholder_label(p)).collect();
+                labels.sort_unstable();
+                labels.dedup();
+                message.push_str(&format!(
+                    "; decoded content contains PII ({}): {}",
+                    labels.join(", "),
+                    redacted
+                ));
+                severity = "error";
+            }
+        }
+        issues.push(Issue {
+            rule: "encoded_blob".into(),
+            message,
+            severity: severity.into(),
+            ..Default::default()
+        });
+    }
+    issues
+}
 
+/// Case-fold `s` for caseless keyword matching.
+///
+/// Extends `to_lowercase` with the Unicode full case-folding mappings that
+/// plain lowercasing misses: `ß`/`ẞ` fold to `ss` (so "STRASSE" matches
+/// "straße"), final `ς` to `σ` and long `ſ` to `s`.  Turkish `İ` and dotless
+/// `ı` both fold to `i`, so "İstanbul", "ISTANBUL" and "ıstanbul" all match
+/// "istanbul" regardless of locale.
+fn fold_case(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            'İ' | 'ı' => folded.push('i'),
+            _ => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+/// One validation finding.
+#[derive(Default)]
+pub struct Issue {
+    pub rule: String,
+    pub message: String,
+    /// `"error"` or `"warning"`.
+    pub severity: String,
+    /// Dotted location in the JSON output (`user.email`, `items[2].id`).
+    pub path: Option<String>,
+    /// Dotted location in the schema of the keyword that failed.
+    pub schema_path: Option<String>,
+}
+
+/// The value at a dotted `path` such as `user.status` or `items[2].id`.
+fn lookup_path<'a>(data: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut current = data;
+    for segment in path.split('.') {
+        let (key, indices) = match segment.find('[') {
+            Some(i) => (&segment[..i], &segment[i..]),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            current = current.get(key)?;
+        }
+        for index in indices.split('[').skip(1) {
+            let index: usize = index.strip_suffix(']')?.parse().ok()?;
+            current = current.get(index)?;
+        }
+    }
+    Some(current)
+}
+
+/// Check each dotted field path in `constraints` against its allowed values.
+///
+/// A missing field or a value outside the allowed set is a
+/// `field_constraint` error carrying the field as `path`.  Non-string values
+/// are compared by their JSON text, so `"true"` allows `true`.
+fn check_field_constraints(
+    data: &serde_json::Value,
+    constraints: &HashMap<String, Vec<String>>,
+) -> Vec<Issue> {
+    let mut fields: Vec<&String> = constraints.keys().collect();
+    fields.sort();
+    let mut issues = Vec::new();
+    for field in fields {
+        let allowed = &constraints[field];
+        let message = match lookup_path(data, field) {
+            None => format!("Required field '{}' is missing", field),
+            Some(value) => {
+                let actual = match value {
+                    serde_json::Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                if allowed.contains(&actual) {
+                    continue;
+                }
+                format!(
+                    "Field '{}' has disallowed value '{}'; allowed: {:?}",
+                    field, actual, allowed
+                )
+            }
+        };
+        issues.push(Issue {
+            rule: "field_constraint".into(),
+            message,
+            severity: "error".into(),
+            path: Some(field.clone()),
+            ..Default::default()
+        });
+    }
+    issues
+}
+
+/// Sum the weights of the blocked keywords found in `text`.
+///
+/// Unlike `blocked_keywords`, a hit does not fail the output on its own: the
+/// matched terms are reported as one `blocked_keyword_score` warning, which
+/// becomes an error once the total reaches `threshold`.
+fn check_weighted_keywords(
+    text: &str,
+    weights: &HashMap<String, f64>,
+    threshold: f64,
+) -> Option<Issue> {
+    let lower_text = fold_case(text);
+    let mut found: Vec<(&String, f64)> = weights
+        .iter()
+        .filter(|(kw, _)| lower_text.contains(&fold_case(kw)))
+        .map(|(kw, w)| (kw, *w))
+        .collect();
+    if found.is_empty() {
+        return None;
+    }
+    found.sort_by(|a, b| a.0.cmp(b.0));
+    let score = round_score(found.iter().map(|(_, w)| w).sum());
+    let terms: Vec<&str> = found.iter().map(|(kw, _)| kw.as_str()).collect();
+    let severity = if score >= threshold {
+        "error"
+    } else {
+        "warning"
+    };
+    Some(Issue {
+        rule: "blocked_keyword_score".into(),
+        message: format!(
+            "Blocked keyword score {} (threshold {}) from: {}",
+            score,
+            threshold,
+            terms.join(", ")
+        ),
+        severity: severity.into(),
+        ..Default::default()
+    })
+}
+
+/// One `field:int[min,max]` or `field:float[min,max]` clause of a
+/// `range_constraints` string.
+#[derive(Debug, PartialEq)]
+struct RangeConstraint {
+    field: String,
+    integer: bool,
+    min: f64,
+    max: f64,
+}
+
+/// Split `spec` on the commas that are not inside brackets, so both the
+/// range bounds and array indices in field paths stay intact.
+fn split_clauses(spec: &str) -> Vec<&str> {
+    let mut clauses = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (i, c) in spec.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                clauses.push(&spec[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    clauses.push(&spec[start..]);
+    clauses
+}
+
+/// Parse a compact range DSL such as `"age:int[0,120], score:float[0,1]"`.
+/// Bounds are inclusive.  Returns an error naming the offending clause.
+fn parse_range_constraints(spec: &str) -> Result<Vec<RangeConstraint>, String> {
+    split_clauses(spec)
+        .into_iter()
+        .map(str::trim)
+        .filter(|clause| !clause.is_empty())
+        .map(|clause| {
+            let invalid = || {
+                format!(
+                    "Invalid range constraint '{}'; expected 'field:int[min,max]' \
+                     or 'field:float[min,max]'",
+                    clause
+                )
+            };
+            let (field, kind) = clause.split_once(':').ok_or_else(invalid)?;
+            let kind = kind.trim();
+            let (integer, bounds) = if let Some(rest) = kind.strip_prefix("int") {
+                (true, rest)
+            } else if let Some(rest) = kind.strip_prefix("float") {
+                (false, rest)
+            } else {
+                return Err(invalid());
+            };
+            let (min, max) = bounds
+                .trim()
+                .strip_prefix('[')
+                .and_then(|b| b.strip_suffix(']'))
+                .and_then(|b| b.split_once(','))
+                .ok_or_else(invalid)?;
+            let min: f64 = min.trim().parse().map_err(|_| invalid())?;
+            let max: f64 = max.trim().parse().map_err(|_| invalid())?;
+            let field = field.trim();
+            if field.is_empty() || min > max {
+                return Err(invalid());
+            }
+            Ok(RangeConstraint {
+                field: field.to_string(),
+                integer,
+                min,
+                max,
+            })
+        })
+        .collect()
+}
+
+/// Check each range constraint against `data`.  A missing field, a value of
+/// the wrong numeric type or one outside the inclusive range is a
+/// `field_constraint` error carrying the field as `path`.
+fn check_range_constraints(
+    data: &serde_json::Value,
+    constraints: &[RangeConstraint],
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    for constraint in constraints {
+        let field = &constraint.field;
+        let kind = if constraint.integer { "int" } else { "float" };
+        let message = match lookup_path(data, field) {
+            None => format!("Required field '{}' is missing", field),
+            Some(value) => {
+                let number = value
+                    .as_f64()
+                    .filter(|_| !constraint.integer || value.is_i64() || value.is_u64());
+                match number {
+                    None => format!("Field '{}' must be {}, got {}", field, kind, value),
+                    Some(n) if n < constraint.min || n > constraint.max => format!(
+                        "Field '{}' value {} is outside {}[{}, {}]",
+                        field, value, kind, constraint.min, constraint.max
+                    ),
+                    Some(_) => continue,
+                }
+            }
+        };
+        issues.push(Issue {
+            rule: "field_constraint".into(),
+            message,
+            severity: "error".into(),
+            path: Some(field.clone()),
+            ..Default::default()
+        });
+    }
+    issues
+}
+
+fn check_json(text: &str, schema_str: &str, include_schema_path: bool) -> Vec<Issue> {
     let data: serde_json::Value = match serde_json::from_str(text) {
         Ok(v) => v,
         Err(e) => {
-            issues.push(Issue {
+            return vec![Issue {
                 rule: "json_schema".into(),
                 message: format!("Output is not valid JSON: {}", e),
                 severity: "error".into(),
-            });
-            return issues;
+                ..Default::default()
+            }];
         }
     };
 
     let schema: serde_json::Value = match serde_json::from_str(schema_str) {
         Ok(v) => v,
         Err(e) => {
-            issues.push(Issue {
+            return vec![Issue {
                 rule: "json_schema".into(),
                 message: format!("Invalid schema JSON: {}", e),
                 severity: "error".into(),
-            });
-            return issues;
+                ..Default::default()
+            }];
         }
     };
 
-    // Check top-level type
-    if let Some(expected_type) = schema.get("type").and_then(|v| v.as_str()) {
-        match expected_type {
-            "object" if !data.is_object() => {
-                issues.push(Issue {
-                    rule: "json_schema".into(),
-                    message: "Expected a JSON object at top level".into(),
-                    severity: "error".into(),
-                });
+    let mut issues = check_value(&data, &schema);
+    if !include_schema_path {
+        for issue in &mut issues {
+            issue.schema_path = None;
+        }
+    }
+    issues
+}
+
+/// Repairs `repair_json` can apply, with the message reported for each.
+const JSON_REPAIRS: &[(&str, &str)] = &[
+    ("code_fence", "stripped a Markdown code fence"),
+    ("trailing_comma", "removed trailing comma(s)"),
+    (
+        "single_quotes",
+        "converted single-quoted strings to double quotes",
+    ),
+    ("unquoted_keys", "quoted bare object keys"),
+    (
+        "python_literals",
+        "replaced True/False/None with true/false/null",
+    ),
+];
+
+/// Fix common near-JSON mistakes made by sloppy models.
+///
+/// Returns the repaired text and the names of the repairs applied (see
+/// `JSON_REPAIRS`), or `None` when `text` is already valid JSON or the fixes
+/// still do not produce valid JSON.
+fn repair_json(text: &str) -> Option<(String, Vec<&'static str>)> {
+    if serde_json::from_str::<serde_json::Value>(text).is_ok() {
+        return None;
+    }
+    let mut repairs: Vec<&'static str> = Vec::new();
+    let mut note = |repair: &'static str| {
+        if !repairs.contains(&repair) {
+            repairs.push(repair);
+        }
+    };
+
+    let mut body = text.trim();
+    if let Some(rest) = body.strip_prefix("```") {
+        if let Some((_, inner)) = rest.split_once('\n') {
+            body = inner.trim_end().trim_end_matches("```").trim();
+            note("code_fence");
+        }
+    }
+
+    let chars: Vec<char> = body.chars().collect();
+    let next_significant = |from: usize| chars[from..].iter().find(|c| !c.is_whitespace());
+    let mut out = String::with_capacity(body.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                // Copy a double-quoted string verbatim.
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 1;
+                    } else if chars[i] == '"' {
+                        break;
+                    }
+                    i += 1;
+                }
             }
-            "array" if !data.is_array() => {
-                issues.push(Issue {
-                    rule: "json_schema".into(),
-                    message: "Expected a JSON array at top level".into(),
-                    severity: "error".into(),
-                });
+            '\'' => {
+                note("single_quotes");
+                out.push('"');
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    match chars[i] {
+                        '\\' if chars.get(i + 1) == Some(&'\'') => {
+                            out.push('\'');
+                            i += 1;
+                        }
+                        '\\' => {
+                            out.push('\\');
+                            if let Some(&escaped) = chars.get(i + 1) {
+                                out.push(escaped);
+                                i += 1;
+                            }
+                        }
+                        '"' => out.push_str("\\\""),
+                        other => out.push(other),
+                    }
+                    i += 1;
+                }
+                out.push('"');
             }
-            _ => {}
+            ',' if matches!(next_significant(i + 1), Some('}' | ']')) => {
+                note("trailing_comma");
+            }
+            c if c.is_alphabetic() || c == '_' || c == '$' => {
+                let start = i;
+                while i + 1 < chars.len()
+                    && (chars[i + 1].is_alphanumeric()
+                        || chars[i + 1] == '_'
+                        || chars[i + 1] == '$')
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..=i].iter().collect();
+                if next_significant(i + 1) == Some(&':') {
+                    note("unquoted_keys");
+                    out.push('"');
+                    out.push_str(&word);
+                    out.push('"');
+                } else {
+                    let literal = match word.as_str() {
+                        "True" => "true",
+                        "False" => "false",
+                        "None" => "null",
+                        other => other,
+                    };
+                    if literal != word {
+                        note("python_literals");
+                    }
+                    out.push_str(literal);
+                }
+            }
+            other => out.push(other),
         }
+        i += 1;
+    }
+
+    serde_json::from_str::<serde_json::Value>(&out)
+        .is_ok()
+        .then_some((out, repairs))
+}
+
+/// One `json_repair` warning per repair applied, so a fix is never silent.
+fn repair_issues(repairs: &[&str]) -> Vec<Issue> {
+    repairs
+        .iter()
+        .map(|repair| {
+            let description = JSON_REPAIRS
+                .iter()
+                .find(|(name, _)| name == repair)
+                .map_or(*repair, |(_, description)| description);
+            Issue {
+                rule: "json_repair".into(),
+                message: format!("Repaired JSON output ({}): {}", repair, description),
+                severity: "warning".into(),
+                ..Default::default()
+            }
+        })
+        .collect()
+}
+
+fn check_value(data: &serde_json::Value, schema: &serde_json::Value) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    check_node(data, schema, "", "", &mut issues);
+    issues
+}
+
+fn join_path(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", parent, key)
+    }
+}
+
+fn type_matches(data: &serde_json::Value, expected: &str) -> bool {
+    match expected {
+        "object" => data.is_object(),
+        "array" => data.is_array(),
+        "string" => data.is_string(),
+        "number" => data.is_number(),
+        "integer" => data.is_i64() || data.is_u64(),
+        "boolean" => data.is_boolean(),
+        "null" => data.is_null(),
+        _ => true,
+    }
+}
+
+/// Recursively validate `data` against `schema`, supporting `type`,
+/// `required`, `properties` and `items`.
+///
+/// `path` is the dotted location of `data` in the output and `schema_path`
+/// the dotted location of `schema` in the schema document.
+fn check_node(
+    data: &serde_json::Value,
+    schema: &serde_json::Value,
+    path: &str,
+    schema_path: &str,
+    issues: &mut Vec<Issue>,
+) {
+    let location = if path.is_empty() {
+        "at top level".to_string()
+    } else {
+        format!("at '{}'", path)
+    };
+    let error = |message: String, keyword: &str| Issue {
+        rule: "json_schema".into(),
+        message,
+        severity: "error".into(),
+        path: Some(path.to_string()),
+        schema_path: Some(join_path(schema_path, keyword)),
+    };
+
+    // Check type (a single name or a list of alternatives)
+    let expected: Vec<&str> = match schema.get("type") {
+        Some(serde_json::Value::String(t)) => vec![t.as_str()],
+        Some(serde_json::Value::Array(ts)) => ts.iter().filter_map(|t| t.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    if !expected.is_empty() && !expected.iter().any(|t| type_matches(data, t)) {
+        let message = match expected.as_slice() {
+            [t] => format!("Expected a JSON {} {}", t, location),
+            ts => format!("Expected one of [{}] {}", ts.join(", "), location),
+        };
+        issues.push(error(message, "type"));
+        return;
     }
 
-    // Check required keys (one level deep)
     if let Some(obj) = data.as_object() {
+        // Check required keys
         if let Some(required) = schema.get("required").and_then(|v| v.as_array()) {
             for key in required {
                 if let Some(key_str) = key.as_str() {
                     if !obj.contains_key(key_str) {
-                        issues.push(Issue {
-                            rule: "json_schema".into(),
-                            message: format!("Required key missing: '{}'", key_str),
-                            severity: "error".into(),
-                        });
+                        issues.push(error(
+                            format!("Required key missing: '{}'", join_path(path, key_str)),
+                            "required",
+                        ));
                     }
                 }
             }
         }
+
+        // Recurse into declared properties
+        if let Some(properties) = schema.get("properties").and_then(|v| v.as_object()) {
+            for (key, sub_schema) in properties {
+                if let Some(value) = obj.get(key) {
+                    check_node(
+                        value,
+                        sub_schema,
+                        &join_path(path, key),
+                        &join_path(schema_path, &format!("properties.{}", key)),
+                        issues,
+                    );
+                }
+            }
+        }
     }
 
-    issues
+    // Recurse into array items
+    if let (Some(items), Some(item_schema)) = (data.as_array(), schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_node(
+                item,
+                item_schema,
+                &format!("{}[{}]", path, i),
+                &join_path(schema_path, "items"),
+                issues,
+            );
+        }
+    }
 }
 
-/// Validate LLM output text against configurable rules.
+#[cfg(feature = "python")]
+pub(crate) fn issues_to_py(py: Python<'_>, issues: &[Issue]) -> PyResult<Py<PyList>> {
+    let py_issues = PyList::empty(py);
+    for issue in issues {
+        let dict = PyDict::new(py);
+        dict.set_item("rule", &issue.rule)?;
+        dict.set_item("message", &issue.message)?;
+        dict.set_item("severity", &issue.severity)?;
+        if let Some(path) = &issue.path {
+            dict.set_item("path", path)?;
+        }
+        if let Some(schema_path) = &issue.schema_path {
+            dict.set_item("schema_path", schema_path)?;
+        }
+        py_issues.append(&dict)?;
+    }
+    Ok(py_issues.unbind())
+}
+
+/// Something the array scanner found in the stream.
+#[cfg(feature = "python")]
+#[derive(Debug, PartialEq)]
+enum ScanEvent {
+    /// A completed top-level element: (zero-based index, raw JSON text).
+    Element(usize, String),
+    /// The stream is not a well-formed top-level array.
+    Malformed(String),
+}
+
+/// Splits a streamed top-level JSON array into its elements.
 ///
-/// Returns (is_valid, issues_list, hallucination_score) where issues_list
-/// is a Python list of dicts with keys: rule, message, severity.
-#[pyfunction]
-#[pyo3(signature = (text, json_schema=None, max_length=None, check_hallucination=true, hallucination_threshold=0.6, required_keywords=None, blocked_keywords=None))]
-pub fn output_validate(
-    py: Python<'_>,
-    text: &str,
-    json_schema: Option<&str>,
-    max_length: Option<usize>,
-    check_hallucination: bool,
-    hallucination_threshold: f64,
-    required_keywords: Option<Vec<String>>,
-    blocked_keywords: Option<Vec<String>>,
-) -> PyResult<(bool, Py<PyList>, f64)> {
+/// Tracks string/escape state and bracket depth so that commas and brackets
+/// inside nested values or string literals do not end an element.
+#[cfg(feature = "python")]
+#[derive(Default)]
+struct ArrayScanner {
+    started: bool,
+    finished: bool,
+    depth: usize,
+    in_string: bool,
+    escaped: bool,
+    element: String,
+    count: usize,
+}
+
+#[cfg(feature = "python")]
+impl ArrayScanner {
+    fn push(&mut self, chunk: &str) -> Vec<ScanEvent> {
+        let mut events = Vec::new();
+        for c in chunk.chars() {
+            if self.finished {
+                if !c.is_whitespace() {
+                    events.push(ScanEvent::Malformed(
+                        "Unexpected content after the closing ']'".into(),
+                    ));
+                    break;
+                }
+                continue;
+            }
+            if !self.started {
+                match c {
+                    '[' => {
+                        self.started = true;
+                        self.depth = 1;
+                    }
+                    c if c.is_whitespace() => {}
+                    _ => {
+                        self.finished = true;
+                        events.push(ScanEvent::Malformed("Expected a JSON array".into()));
+                        break;
+                    }
+                }
+                continue;
+            }
+            if self.in_string {
+                self.element.push(c);
+                if self.escaped {
+                    self.escaped = false;
+                } else if c == '\\' {
+                    self.escaped = true;
+                } else if c == '"' {
+                    self.in_string = false;
+                }
+                continue;
+            }
+            match c {
+                '"' => {
+                    self.in_string = true;
+                    self.element.push(c);
+                }
+                '{' | '[' => {
+                    self.depth += 1;
+                    self.element.push(c);
+                }
+                ']' if self.depth == 1 => {
+                    self.finished = true;
+                    events.extend(self.flush());
+                }
+                '}' | ']' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    self.element.push(c);
+                }
+                ',' if self.depth == 1 => events.extend(self.flush()),
+                _ => self.element.push(c),
+            }
+        }
+        events
+    }
+
+    fn flush(&mut self) -> Option<ScanEvent> {
+        let element = std::mem::take(&mut self.element);
+        let element = element.trim();
+        if element.is_empty() {
+            return None;
+        }
+        let index = self.count;
+        self.count += 1;
+        Some(ScanEvent::Element(index, element.to_string()))
+    }
+}
+
+#[cfg(feature = "python")]
+fn check_element(index: usize, text: &str, items_schema: Option<&serde_json::Value>) -> Vec<Issue> {
+    let data: serde_json::Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(e) => {
+            return vec![Issue {
+                rule: "json_schema".into(),
+                message: format!("Element {} is not valid JSON: {}", index, e),
+                severity: "error".into(),
+                ..Default::default()
+            }];
+        }
+    };
+    let Some(schema) = items_schema else {
+        return Vec::new();
+    };
+    check_value(&data, schema)
+        .into_iter()
+        .map(|issue| Issue {
+            message: format!("Element {}: {}", index, issue.message),
+            path: issue.path.map(|p| match p.as_str() {
+                "" => format!("[{}]", index),
+                p => format!("[{}].{}", index, p),
+            }),
+            schema_path: None,
+            ..issue
+        })
+        .collect()
+}
+
+/// Validate a streamed JSON array element-by-element.
+///
+/// Feed chunks as they arrive; each call returns the issues (same dict shape
+/// as `output_validate`) for the array elements completed by that chunk, so
+/// callers can process valid elements early and abort on the first invalid
+/// one.  `items_schema` is checked against each element.
+#[cfg(feature = "python")]
+#[pyclass]
+pub struct StreamingOutputValidator {
+    items_schema: Option<serde_json::Value>,
+    scanner: ArrayScanner,
+    has_errors: bool,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl StreamingOutputValidator {
+    #[new]
+    #[pyo3(signature = (items_schema=None))]
+    fn new(items_schema: Option<&str>) -> PyResult<Self> {
+        let items_schema = items_schema
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| PyValueError::new_err(format!("Invalid schema JSON: {}", e)))?;
+        Ok(Self {
+            items_schema,
+            scanner: ArrayScanner::default(),
+            has_errors: false,
+        })
+    }
+
+    /// Consume `chunk` and return issues for any newly completed elements.
+    fn feed(&mut self, py: Python<'_>, chunk: &str) -> PyResult<Py<PyList>> {
+        let issues = self.feed_str(chunk);
+        issues_to_py(py, &issues)
+    }
+
+    /// Number of top-level elements completed so far.
+    #[getter]
+    fn elements_seen(&self) -> usize {
+        self.scanner.count
+    }
+
+    /// `False` once any element (or the array itself) failed validation.
+    #[getter]
+    fn is_valid(&self) -> bool {
+        !self.has_errors
+    }
+}
+
+#[cfg(feature = "python")]
+impl StreamingOutputValidator {
+    fn feed_str(&mut self, chunk: &str) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        for event in self.scanner.push(chunk) {
+            match event {
+                ScanEvent::Element(index, text) => {
+                    issues.extend(check_element(index, &text, self.items_schema.as_ref()));
+                }
+                ScanEvent::Malformed(message) => issues.push(Issue {
+                    rule: "json_schema".into(),
+                    message,
+                    severity: "error".into(),
+                    ..Default::default()
+                }),
+            }
+        }
+        self.has_errors |= issues.iter().any(|i| i.severity == "error");
+        issues
+    }
+}
+
+/// Validation outcome: `(is_valid, issues, hallucination_score, repaired_json)`.
+pub type Validation = (bool, Vec<Issue>, f64, Option<String>);
+
+/// Options for [`validate`].  `Default` matches the keyword defaults of
+/// `output_validate`, whose docs describe each check; collections left empty
+/// disable their check.
+#[derive(Clone, Debug)]
+pub struct ValidateOptions {
+    /// JSON Schema the output must conform to.
+    pub json_schema: Option<String>,
+    /// Maximum output length in bytes.
+    pub max_length: Option<usize>,
+    /// Score hedging language as a hallucination signal.
+    pub check_hallucination: bool,
+    /// Hallucination score at which a `hallucination` warning is reported.
+    pub hallucination_threshold: f64,
+    /// Terms the output must contain (case-folded).
+    pub required_keywords: Vec<String>,
+    /// Terms the output must not contain (case-folded).
+    pub blocked_keywords: Vec<String>,
+    /// ISO 639-1 code the output is expected to be written in.
+    pub expected_language: Option<String>,
+    /// Add the failing keyword's `schema_path` to JSON-schema issues.
+    pub include_schema_path: bool,
+    /// Fail answers of more than a few words that contain no citation.
+    pub require_citations: bool,
+    /// Regex a citation must match; `None` gives a `[n]` marker or a URL.
+    pub citation_pattern: Option<String>,
+    /// Repair near-JSON output before the schema check.
+    pub attempt_repair: bool,
+    /// Warn about redaction placeholders left in the output.
+    pub check_placeholder_leak: bool,
+    /// Placeholder delimiters for that check; `None` gives `<<` and `>>`.
+    pub placeholder_delimiters: Option<(String, String)>,
+    /// `pii_redact` placeholder template for that check, instead of the
+    /// delimiters.
+    pub placeholder_template: Option<String>,
+    /// Report long base64/hex runs.
+    pub check_encoded_blobs: bool,
+    /// Allowed values by dotted JSON field path.
+    pub field_constraints: HashMap<String, Vec<String>>,
+    /// Risk weights of terms summed into a `blocked_keyword_score` issue.
+    pub blocked_keyword_weights: HashMap<String, f64>,
+    /// Weighted-keyword score at which that issue is an error.
+    pub blocked_keyword_threshold: f64,
+    /// Fail output that is JSON, fenced code or HTML.
+    pub expect_plain_text: bool,
+    /// Report individual sentences dense in hedging phrases.
+    pub per_sentence_hedging: bool,
+    /// Hedging phrases per word at which a sentence is reported.
+    pub sentence_hedging_threshold: f64,
+    /// Numeric range checks such as `"age:int[0,120]"`.
+    pub range_constraints: Option<String>,
+    /// Report empty or whitespace-only output.
+    pub check_nonempty: bool,
+    /// Report the hallucination score on the unified scale.
+    pub unified_scale: bool,
+}
+
+impl Default for ValidateOptions {
+    fn default() -> Self {
+        Self {
+            json_schema: None,
+            max_length: None,
+            check_hallucination: true,
+            hallucination_threshold: 0.6,
+            required_keywords: Vec::new(),
+            blocked_keywords: Vec::new(),
+            expected_language: None,
+            include_schema_path: false,
+            require_citations: false,
+            citation_pattern: None,
+            attempt_repair: false,
+            check_placeholder_leak: false,
+            placeholder_delimiters: None,
+            placeholder_template: None,
+            check_encoded_blobs: false,
+            field_constraints: HashMap::new(),
+            blocked_keyword_weights: HashMap::new(),
+            blocked_keyword_threshold: 1.0,
+            expect_plain_text: false,
+            per_sentence_hedging: false,
+            sentence_hedging_threshold: 0.2,
+            range_constraints: None,
+            check_nonempty: true,
+            unified_scale: false,
+        }
+    }
+}
+
+/// Validate `text` under `opts`: the pure-Rust core of `output_validate`.
+/// A malformed `range_constraints`, `citation_pattern` or
+/// `placeholder_template` is an error.
+pub fn validate(text: &str, opts: &ValidateOptions) -> Result<Validation, String> {
+    let ranges = opts
+        .range_constraints
+        .as_deref()
+        .map(parse_range_constraints)
+        .transpose()?
+        .filter(|r| !r.is_empty());
     let mut issues: Vec<Issue> = Vec::new();
     let mut h_score = 0.0f64;
 
+    // 0. Empty output
+    if opts.check_nonempty {
+        issues.extend(check_nonempty_text(text));
+    }
+
     // 1. Max-length check
-    if let Some(max_len) = max_length {
+    if let Some(max_len) = opts.max_length {
         if text.len() > max_len {
             issues.push(Issue {
                 rule: "max_length".into(),
@@ -145,19 +1415,32 @@ pub fn output_validate(
                     max_len
                 ),
                 severity: "error".into(),
+                ..Default::default()
             });
         }
     }
 
     // 2. JSON-schema validation
-    if let Some(schema_str) = json_schema {
-        issues.extend(check_json(text, schema_str));
+    let mut repaired = None;
+    if let Some(schema_str) = &opts.json_schema {
+        match opts.attempt_repair.then(|| repair_json(text)).flatten() {
+            Some((fixed, repairs)) => {
+                issues.extend(repair_issues(&repairs));
+                issues.extend(check_json(&fixed, schema_str, opts.include_schema_path));
+                repaired = Some(fixed);
+            }
+            None => issues.extend(check_json(text, schema_str, opts.include_schema_path)),
+        }
     }
 
     // 3. Hallucination scoring
-    if check_hallucination {
-        h_score = hallucination_score(text);
-        if h_score >= hallucination_threshold {
+    if opts.check_hallucination {
+        h_score = scaled(
+            hallucination_score(text),
+            Detector::Hallucination,
+            opts.unified_scale,
+        );
+        if h_score >= opts.hallucination_threshold {
             issues.push(Issue {
                 rule: "hallucination".into(),
                 message: format!(
@@ -165,53 +1448,231 @@ pub fn output_validate(
                     h_score
                 ),
                 severity: "warning".into(),
+                ..Default::default()
             });
         }
     }
 
+    // 3b. Sentence-level hedging
+    if opts.per_sentence_hedging {
+        issues.extend(check_sentence_hedging(
+            text,
+            opts.sentence_hedging_threshold,
+        ));
+    }
+
     // 4. Required keywords
-    if let Some(ref keywords) = required_keywords {
-        let lower_text = text.to_lowercase();
-        for kw in keywords {
-            if !lower_text.contains(&kw.to_lowercase()) {
+    if !opts.required_keywords.is_empty() {
+        let lower_text = fold_case(text);
+        for kw in &opts.required_keywords {
+            if !lower_text.contains(&fold_case(kw)) {
                 issues.push(Issue {
                     rule: "required_keyword".into(),
                     message: format!("Required keyword missing: '{}'", kw),
                     severity: "error".into(),
+                    ..Default::default()
                 });
             }
         }
     }
 
     // 5. Blocked keywords
-    if let Some(ref keywords) = blocked_keywords {
-        let lower_text = text.to_lowercase();
-        for kw in keywords {
-            if lower_text.contains(&kw.to_lowercase()) {
+    if !opts.blocked_keywords.is_empty() {
+        let lower_text = fold_case(text);
+        for kw in &opts.blocked_keywords {
+            if lower_text.contains(&fold_case(kw)) {
                 issues.push(Issue {
                     rule: "blocked_keyword".into(),
                     message: format!("Blocked keyword found: '{}'", kw),
                     severity: "error".into(),
+                    ..Default::default()
                 });
             }
         }
     }
 
-    let has_errors = issues.iter().any(|i| i.severity == "error");
+    // 5b. Weighted blocked keywords
+    issues.extend(check_weighted_keywords(
+        text,
+        &opts.blocked_keyword_weights,
+        opts.blocked_keyword_threshold,
+    ));
 
-    // Convert issues to Python list of dicts
-    let py_issues = PyList::empty(py);
-    for issue in &issues {
-        let dict = PyDict::new(py);
-        dict.set_item("rule", &issue.rule)?;
-        dict.set_item("message", &issue.message)?;
-        dict.set_item("severity", &issue.severity)?;
-        py_issues.append(&dict)?;
+    // 6. Response language
+    if let Some(expected) = &opts.expected_language {
+        issues.extend(check_language(text, expected));
+    }
+
+    // 7. Citations
+    if opts.require_citations {
+        let pattern = opts
+            .citation_pattern
+            .as_deref()
+            .unwrap_or(DEFAULT_CITATION_PATTERN);
+        let pattern =
+            Regex::new(pattern).map_err(|e| format!("Invalid citation pattern: {}", e))?;
+        issues.extend(check_citations(text, &pattern));
     }
 
-    let h_score = (h_score * 10_000.0).round() / 10_000.0;
+    // 8. Leaked redaction placeholders
+    if opts.check_placeholder_leak {
+        let pattern = placeholder_leak_pattern(
+            opts.placeholder_delimiters.as_ref(),
+            opts.placeholder_template.as_deref(),
+        )?;
+        issues.extend(find_placeholder_leaks(text, &pattern));
+    }
+
+    // 9. Encoded blobs
+    if opts.check_encoded_blobs {
+        issues.extend(find_encoded_blobs(text));
+    }
 
-    Ok((!has_errors, py_issues.unbind(), h_score))
+    // 10. Field constraints
+    let field_constraints = Some(&opts.field_constraints).filter(|c| !c.is_empty());
+    if field_constraints.is_some() || ranges.is_some() {
+        match serde_json::from_str(repaired.as_deref().unwrap_or(text)) {
+            Ok(data) => {
+                if let Some(constraints) = field_constraints {
+                    issues.extend(check_field_constraints(&data, constraints));
+                }
+                if let Some(ranges) = &ranges {
+                    issues.extend(check_range_constraints(&data, ranges));
+                }
+            }
+            // An unparsable output is already reported by the schema check.
+            Err(e) if opts.json_schema.is_none() => issues.push(Issue {
+                rule: "field_constraint".into(),
+                message: format!("Output is not valid JSON: {}", e),
+                severity: "error".into(),
+                ..Default::default()
+            }),
+            Err(_) => {}
+        }
+    }
+
+    // 11. Plain-text format
+    if opts.expect_plain_text {
+        issues.extend(check_plain_text(text));
+    }
+
+    let has_errors = issues.iter().any(|i| i.severity == "error");
+    Ok((!has_errors, issues, h_score, repaired))
+}
+
+/// Validate LLM output text against configurable rules.
+///
+/// Returns (is_valid, issues_list, hallucination_score) where issues_list
+/// is a Python list of dicts with keys: rule, message, severity.  JSON-schema
+/// issues also carry `path` (dotted data path) and, with
+/// `include_schema_path`, `schema_path` (dotted path of the failing keyword).
+///
+/// With `attempt_repair` (and a `json_schema`), near-JSON output with trailing
+/// commas, single quotes, bare keys, Python literals or a code fence is
+/// repaired before validation.  Each applied fix is reported as a
+/// `json_repair` warning and the repaired JSON is returned as the fourth
+/// tuple element (`None` when no repair was made).
+///
+/// With `check_placeholder_leak`, every redaction placeholder such as
+/// `<<SSN_1>>` found in the output is reported as a `placeholder_leak`
+/// warning; `placeholder_delimiters` overrides the `("<<", ">>")` delimiters.
+/// Output redacted with a `placeholder_template` (see `pii_redact`) is checked
+/// by passing the same `placeholder_template`, e.g. `"[REDACTED:{label}:{n}]"`;
+/// it cannot be combined with `placeholder_delimiters`, and a template
+/// `pii_redact` would reject raises `ValueError`.
+///
+/// With `check_encoded_blobs`, long base64 or hex runs are reported as
+/// `encoded_blob` warnings with their byte offset.  Blobs that decode to text
+/// containing PII are errors, and the message lists the PII (redacted).
+///
+/// With `require_citations`, answers of at least a few words must match
+/// `citation_pattern` (default: a `[n]` marker or a URL) or a `citation`
+/// error is reported.
+///
+/// `field_constraints` maps dotted field paths (`status`, `user.role`,
+/// `items[0].kind`) to their allowed string values.  The (repaired) output is
+/// parsed as JSON and each missing field or disallowed value is reported as
+/// a `field_constraint` error with the field as `path`.
+///
+/// `range_constraints` is a lighter alternative to a schema for numeric
+/// fields: `"age:int[0,120], score:float[0,1]"` requires each field to be a
+/// number of that type within the inclusive range.  Violations are
+/// `field_constraint` errors; a malformed string raises `ValueError`.
+///
+/// `blocked_keyword_weights` maps terms to a risk weight.  The weights of the
+/// terms found are summed into a single `blocked_keyword_score` warning,
+/// which is an error once the sum reaches `blocked_keyword_threshold`.
+///
+/// With `expect_plain_text`, output that is a JSON object or array, opens
+/// with a code fence or contains HTML tags is reported as a `format` error.
+///
+/// `check_nonempty` (on by default) reports empty or whitespace-only output
+/// as an `empty_output` error, whatever the other checks say.
+///
+/// With `unified_scale`, the returned hallucination score -- and so
+/// `hallucination_threshold` -- is on the scale shared with the injection
+/// and bias scores, where 0.5 is the default threshold.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (text, json_schema=None, max_length=None, check_hallucination=true, hallucination_threshold=0.6, required_keywords=None, blocked_keywords=None, expected_language=None, include_schema_path=false, require_citations=false, citation_pattern=None, attempt_repair=false, check_placeholder_leak=false, placeholder_delimiters=None, check_encoded_blobs=false, field_constraints=None, blocked_keyword_weights=None, blocked_keyword_threshold=1.0, expect_plain_text=false, per_sentence_hedging=false, sentence_hedging_threshold=0.2, range_constraints=None, check_nonempty=true, unified_scale=false, placeholder_template=None))]
+pub fn output_validate(
+    py: Python<'_>,
+    text: &str,
+    json_schema: Option<&str>,
+    max_length: Option<usize>,
+    check_hallucination: bool,
+    hallucination_threshold: f64,
+    required_keywords: Option<Vec<String>>,
+    blocked_keywords: Option<Vec<String>>,
+    expected_language: Option<&str>,
+    include_schema_path: bool,
+    require_citations: bool,
+    citation_pattern: Option<&str>,
+    attempt_repair: bool,
+    check_placeholder_leak: bool,
+    placeholder_delimiters: Option<(String, String)>,
+    check_encoded_blobs: bool,
+    field_constraints: Option<HashMap<String, Vec<String>>>,
+    blocked_keyword_weights: Option<HashMap<String, f64>>,
+    blocked_keyword_threshold: f64,
+    expect_plain_text: bool,
+    per_sentence_hedging: bool,
+    sentence_hedging_threshold: f64,
+    range_constraints: Option<&str>,
+    check_nonempty: bool,
+    unified_scale: bool,
+    placeholder_template: Option<&str>,
+) -> PyResult<(bool, Py<PyList>, f64, Option<String>)> {
+    let opts = ValidateOptions {
+        json_schema: json_schema.map(String::from),
+        max_length,
+        check_hallucination,
+        hallucination_threshold,
+        required_keywords: required_keywords.unwrap_or_default(),
+        blocked_keywords: blocked_keywords.unwrap_or_default(),
+        expected_language: expected_language.map(String::from),
+        include_schema_path,
+        require_citations,
+        citation_pattern: citation_pattern.map(String::from),
+        attempt_repair,
+        check_placeholder_leak,
+        placeholder_delimiters,
+        placeholder_template: placeholder_template.map(String::from),
+        check_encoded_blobs,
+        field_constraints: field_constraints.unwrap_or_default(),
+        blocked_keyword_weights: blocked_keyword_weights.unwrap_or_default(),
+        blocked_keyword_threshold,
+        expect_plain_text,
+        per_sentence_hedging,
+        sentence_hedging_threshold,
+        range_constraints: range_constraints.map(String::from),
+        check_nonempty,
+        unified_scale,
+    };
+    let (is_valid, issues, h_score, repaired) =
+        validate(text, &opts).map_err(PyValueError::new_err)?;
+    Ok((is_valid, issues_to_py(py, &issues)?, h_score, repaired))
 }
 
 #[cfg(test)]
@@ -230,9 +1691,377 @@ mod tests {
         assert_eq!(score, 0.0);
     }
 
+    #[test]
+    fn test_hedging_matches_reported() {
+        let text = "I think it is Paris, but maybe Lyon. Perhaps I'm not sure.";
+        let matches = hedging_matches(text);
+        let phrases: Vec<_> = matches.iter().map(|(p, _, _)| p.as_str()).collect();
+        assert_eq!(phrases, vec!["I think", "maybe", "Perhaps", "I'm not sure"]);
+        for (phrase, start, end) in &matches {
+            assert_eq!(&text[*start..*end], phrase);
+        }
+        assert!(hedging_matches("Paris is the capital of France.").is_empty());
+    }
+
+    #[test]
+    fn test_contradiction_raises_score() {
+        let consistent = "The bridge opened in 1932. The bridge is made of steel.";
+        let contradicting = "The bridge opened in 1932. Actually, the bridge did not open in 1932.";
+        assert_eq!(count_contradictions(consistent), 0);
+        assert_eq!(count_contradictions(contradicting), 0);
+
+        let contradicting =
+            "The bridge is made of steel. Actually, the bridge isn't made of steel.";
+        assert_eq!(count_contradictions(contradicting), 1);
+        assert!(hallucination_score(contradicting) > hallucination_score(consistent));
+
+        assert_eq!(count_contradictions("X is true. X is false."), 1);
+        // Repeating a claim is not a contradiction.
+        assert_eq!(count_contradictions("The sky is blue. The sky is blue."), 0);
+    }
+
+    #[test]
+    fn test_sentence_hedging() {
+        let text = "Paris is the capital of France. I think it might be raining there, \
+                    probably. The Louvre is a museum.";
+        let issues = check_sentence_hedging(text, 0.2);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "hedging");
+        assert!(issues[0].message.starts_with("Sentence at byte 32 "));
+        assert!(issues[0]
+            .message
+            .ends_with("I think, it might be, probably"));
+    }
+
     #[test]
     fn test_empty_text() {
         let score = hallucination_score("");
         assert_eq!(score, 0.0);
     }
+
+    #[test]
+    fn test_language_mismatch() {
+        let french = "Le chat est sur la table et les enfants sont dans le jardin avec nous.";
+        assert_eq!(guess_language(french), Some("fr"));
+        let issues = check_language(french, "en");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "language_mismatch");
+        assert!(issues[0].message.contains("'fr'"));
+
+        let english = "The cat is on the table and the children are in the garden.";
+        assert!(check_language(english, "en-US").is_empty());
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_streaming_array_elements() {
+        let schema = serde_json::json!({"type": "object", "required": ["name"]});
+        let mut validator = StreamingOutputValidator {
+            items_schema: Some(schema),
+            scanner: ArrayScanner::default(),
+            has_errors: false,
+        };
+
+        // First element arrives split across chunks and contains brackets in a string.
+        assert!(validator.feed_str("[{\"name\": \"a [b] {c}\", ").is_empty());
+        assert!(validator.feed_str("\"tags\": [1, 2]}").is_empty());
+        assert!(validator.feed_str(", {\"title\": \"x\\\"]\"").is_empty());
+        assert!(validator.is_valid());
+        assert_eq!(validator.elements_seen(), 1);
+
+        let issues = validator.feed_str("}]");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("Element 1:"));
+        assert!(issues[0].message.contains("'name'"));
+        assert_eq!(issues[0].path.as_deref(), Some("[1]"));
+        assert!(!validator.is_valid());
+        assert_eq!(validator.elements_seen(), 2);
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_streaming_rejects_non_array() {
+        let mut scanner = ArrayScanner::default();
+        assert!(matches!(
+            scanner.push("{\"a\": 1}").as_slice(),
+            [ScanEvent::Malformed(_)]
+        ));
+    }
+
+    #[test]
+    fn test_citations_required() {
+        let pattern = Regex::new(DEFAULT_CITATION_PATTERN).unwrap();
+        let uncited = "The Eiffel Tower was completed in 1889 for the World's Fair.";
+        let issues = check_citations(uncited, &pattern);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "citation");
+        assert_eq!(issues[0].severity, "error");
+
+        let cited = "The Eiffel Tower was completed in 1889 for the World's Fair [1].";
+        assert!(check_citations(cited, &pattern).is_empty());
+        let linked = "See https://example.com/eiffel for the full construction history.";
+        assert!(check_citations(linked, &pattern).is_empty());
+        // Short replies are exempt.
+        assert!(check_citations("Yes, it is.", &pattern).is_empty());
+    }
+
+    #[test]
+    fn test_repair_trailing_comma() {
+        let (fixed, repairs) = repair_json(r#"{"a": 1, "b": [1, 2,],}"#).unwrap();
+        assert_eq!(fixed, r#"{"a": 1, "b": [1, 2]}"#);
+        assert_eq!(repairs, vec!["trailing_comma"]);
+        let issues = repair_issues(&repairs);
+        assert_eq!(issues[0].rule, "json_repair");
+        assert_eq!(issues[0].severity, "warning");
+    }
+
+    #[test]
+    fn test_repair_quotes_keys_and_fence() {
+        let text = "```json\n{name: 'O\\'Brien', \"ok\": True, 'note': 'say \"hi\"'}\n```";
+        let (fixed, repairs) = repair_json(text).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&fixed).unwrap();
+        assert_eq!(value["name"], "O'Brien");
+        assert_eq!(value["ok"], true);
+        assert_eq!(value["note"], "say \"hi\"");
+        assert_eq!(
+            repairs,
+            vec![
+                "code_fence",
+                "unquoted_keys",
+                "single_quotes",
+                "python_literals"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_repair_gives_up() {
+        assert!(repair_json(r#"{"a": 1"#).is_none());
+        assert!(repair_json("not json at all").is_none());
+        // Valid JSON needs no repair.
+        assert!(repair_json(r#"{"a": 1}"#).is_none());
+    }
+
+    #[test]
+    fn test_placeholder_leak() {
+        let default = placeholder_leak_pattern(None, None).unwrap();
+        let issues = find_placeholder_leaks("Your SSN <<SSN_1>> is on file, <<SSN_1>>.", &default);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "placeholder_leak");
+        assert_eq!(issues[0].severity, "warning");
+        assert!(issues[0].message.contains("<<SSN_1>>"));
+
+        assert!(find_placeholder_leaks("No placeholders << here >>.", &default).is_empty());
+        let delimiters = ("[[".to_string(), "]]".to_string());
+        let bracketed = placeholder_leak_pattern(Some(&delimiters), None).unwrap();
+        assert_eq!(
+            find_placeholder_leaks("Hi [[EMAIL_2]]", &bracketed).len(),
+            1
+        );
+
+        // Placeholders from a custom template, as pii_redact produces them.
+        let template = "[REDACTED:{label}:{n}]";
+        let opts = RedactOptions::default()
+            .with_placeholder_template(template)
+            .unwrap();
+        let (redacted, _) = redact("Mail alice@example.com or bob@example.com.", &opts);
+        let templated = placeholder_leak_pattern(None, Some(template)).unwrap();
+        let issues = find_placeholder_leaks(&redacted, &templated);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("[REDACTED:EMAIL:1]"));
+        assert!(find_placeholder_leaks(&redacted, &default).is_empty());
+
+        assert!(placeholder_leak_pattern(Some(&delimiters), Some(template)).is_err());
+        assert!(placeholder_leak_pattern(None, Some("[REDACTED:{label}]")).is_err());
+    }
+
+    #[test]
+    fn test_fold_case_eszett_and_turkish_i() {
+        assert!(fold_case("Die STRASSE ist lang").contains(&fold_case("straße")));
+        assert!(fold_case("Wir wohnen in der Straße").contains(&fold_case("STRASSE")));
+        assert_eq!(fold_case("İSTANBUL"), "istanbul");
+        assert_eq!(fold_case("ıstanbul"), "istanbul");
+        assert!(!fold_case("Paris").contains(&fold_case("istanbul")));
+    }
+
+    #[test]
+    fn test_nested_type_error_paths() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "user": {
+                    "type": "object",
+                    "required": ["id"],
+                    "properties": {"email": {"type": "string"}}
+                }
+            }
+        }"#;
+        let issues = check_json(r#"{"user": {"id": 1, "email": 42}}"#, schema, true);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].path.as_deref(), Some("user.email"));
+        assert_eq!(
+            issues[0].schema_path.as_deref(),
+            Some("properties.user.properties.email.type")
+        );
+
+        let issues = check_json(r#"{"user": {"email": "a@b.c"}}"#, schema, false);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].message, "Required key missing: 'user.id'");
+        assert!(issues[0].schema_path.is_none());
+    }
+
+    #[test]
+    fn test_encoded_blobs() {
+        // base64("Contact me at jane.doe@example.com")
+        let text = "Here you go: Q29udGFjdCBtZSBhdCBqYW5lLmRvZUBleGFtcGxlLmNvbQ== enjoy.";
+        let issues = find_encoded_blobs(text);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "encoded_blob");
+        assert_eq!(issues[0].severity, "error");
+        assert!(issues[0].message.contains("base64-encoded blob at byte 13"));
+        assert!(issues[0]
+            .message
+            .contains("(EMAIL): Contact me at <<EMAIL_1>>"));
+
+        let hash = "Digest: 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+        let issues = find_encoded_blobs(hash);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, "warning");
+        assert!(issues[0].message.starts_with("Possible hex-encoded blob"));
+
+        let plain =
+            "Internationalisation works; see https://example.com/docs/getting-started/v2/install";
+        assert!(find_encoded_blobs(plain).is_empty());
+    }
+
+    #[test]
+    fn test_field_constraints() {
+        let data: serde_json::Value = serde_json::from_str(
+            r#"{"status": "archived", "user": {"role": "admin"}, "items": [{"ok": true}]}"#,
+        )
+        .unwrap();
+        let mut constraints = HashMap::new();
+        constraints.insert(
+            "status".to_string(),
+            vec!["active".to_string(), "pending".to_string()],
+        );
+        constraints.insert("user.role".to_string(), vec!["admin".to_string()]);
+        constraints.insert("items[0].ok".to_string(), vec!["true".to_string()]);
+        constraints.insert("priority".to_string(), vec!["high".to_string()]);
+
+        let issues = check_field_constraints(&data, &constraints);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].path.as_deref(), Some("priority"));
+        assert_eq!(issues[0].message, "Required field 'priority' is missing");
+        assert_eq!(issues[1].rule, "field_constraint");
+        assert_eq!(issues[1].path.as_deref(), Some("status"));
+        assert!(issues[1].message.contains("disallowed value 'archived'"));
+    }
+
+    #[test]
+    fn test_weighted_blocked_keywords() {
+        let mut weights = HashMap::new();
+        weights.insert("refund".to_string(), 0.3);
+        weights.insert("lawsuit".to_string(), 0.4);
+        weights.insert("password".to_string(), 1.0);
+
+        let issue =
+            check_weighted_keywords("A Refund or a lawsuit may follow.", &weights, 1.0).unwrap();
+        assert_eq!(issue.rule, "blocked_keyword_score");
+        assert_eq!(issue.severity, "warning");
+        assert!(issue.message.contains("lawsuit, refund"));
+
+        let issue = check_weighted_keywords("Your password is hunter2.", &weights, 1.0).unwrap();
+        assert_eq!(issue.severity, "error");
+
+        assert!(check_weighted_keywords("All good here.", &weights, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_expect_plain_text() {
+        let json = check_plain_text(r#"{"answer": "Paris is the capital of France."}"#);
+        assert_eq!(json.len(), 1);
+        assert_eq!(json[0].rule, "format");
+        assert!(json[0].message.contains("JSON"));
+
+        let fenced = check_plain_text("```\nParis is the capital of France.\n```");
+        assert!(fenced[0].message.contains("code fence"));
+
+        assert!(!check_plain_text("<p>Paris</p>").is_empty());
+        assert!(check_plain_text("Paris is the capital of France.").is_empty());
+        assert!(check_plain_text("Ask <<NAME_1>> if 3 < 4 > 2.").is_empty());
+    }
+
+    #[test]
+    fn test_nonempty_output() {
+        for blank in ["", "   ", "\n\t \n", "\u{200B}\u{200B}"] {
+            let issue = check_nonempty_text(blank).unwrap();
+            assert_eq!(issue.rule, "empty_output");
+            assert_eq!(issue.severity, "error");
+        }
+        assert!(check_nonempty_text("Paris.").is_none());
+        assert!(check_nonempty_text("  ok  ").is_none());
+    }
+
+    #[test]
+    fn test_range_constraints() {
+        let spec = "age:int[0,120], score:float[0,1], items[0].n:int[1,3]";
+        let ranges = parse_range_constraints(spec).unwrap();
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[2].field, "items[0].n");
+
+        let data: serde_json::Value =
+            serde_json::from_str(r#"{"age": 150, "score": 0.5, "items": [{"n": 2.5}]}"#).unwrap();
+        let issues = check_range_constraints(&data, &ranges);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].rule, "field_constraint");
+        assert_eq!(
+            issues[0].message,
+            "Field 'age' value 150 is outside int[0, 120]"
+        );
+        assert_eq!(issues[1].path.as_deref(), Some("items[0].n"));
+    }
+
+    #[test]
+    fn test_malformed_range_constraints() {
+        for spec in [
+            "age:int[0,120",
+            "age:number[0,1]",
+            "age[0,1]",
+            "age:int[5,1]",
+        ] {
+            let err = parse_range_constraints(spec).unwrap_err();
+            assert!(err.starts_with("Invalid range constraint"), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn test_validate_options() {
+        let (is_valid, issues, h_score, repaired) = validate(
+            "The capital of France is Paris.",
+            &ValidateOptions::default(),
+        )
+        .unwrap();
+        assert!(is_valid);
+        assert!(issues.is_empty());
+        assert_eq!(h_score, 0.0);
+        assert!(repaired.is_none());
+
+        let opts = ValidateOptions {
+            json_schema: Some(r#"{"type": "object", "required": ["name"]}"#.into()),
+            attempt_repair: true,
+            blocked_keywords: vec!["secret".into()],
+            ..Default::default()
+        };
+        let (is_valid, issues, _, repaired) = validate("{'name': 'secret',}", &opts).unwrap();
+        assert!(!is_valid);
+        assert_eq!(repaired.as_deref(), Some(r#"{"name": "secret"}"#));
+        assert!(issues.iter().any(|i| i.rule == "blocked_keyword"));
+
+        let opts = ValidateOptions {
+            range_constraints: Some("age:int[0,120".into()),
+            ..Default::default()
+        };
+        assert!(validate("{}", &opts).is_err());
+    }
 }
@@ -0,0 +1,130 @@
+//! Thread-parallel processing for the batch entry points (`pii_redact_batch`).
+//!
+//! A batch shorter than `SERIAL_BATCH_THRESHOLD` items runs on the calling
+//! thread, where spawning threads would cost more than it saves.  Longer
+//! batches are split into contiguous chunks, one per worker thread, and the
+//! results are returned in input order.  The worker count defaults to the
+//! `LLM_GUARDRAILS_THREADS` environment variable when it holds a positive
+//! integer at first use, otherwise to the available parallelism, and can be
+//! changed at runtime with `set_batch_threads`.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use once_cell::sync::Lazy;
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Batches with fewer items than this are processed serially.
+pub const SERIAL_BATCH_THRESHOLD: usize = 64;
+
+/// Environment variable read once for the initial worker count.
+pub const THREADS_ENV_VAR: &str = "LLM_GUARDRAILS_THREADS";
+
+static BATCH_THREADS: Lazy<AtomicUsize> = Lazy::new(|| AtomicUsize::new(default_threads()));
+
+/// `LLM_GUARDRAILS_THREADS` if it is a positive integer, else the available
+/// parallelism (1 when that is unknown).
+fn default_threads() -> usize {
+    std::env::var(THREADS_ENV_VAR)
+        .ok()
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&threads| threads > 0)
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, usize::from))
+}
+
+/// Set the number of worker threads (at least 1) used for batches of
+/// `SERIAL_BATCH_THRESHOLD` items or more.  `1` processes every batch on the
+/// calling thread.  The setting is process-wide.
+#[cfg(feature = "python")]
+#[pyfunction]
+pub fn set_batch_threads(threads: usize) -> PyResult<()> {
+    store_batch_threads(threads).map_err(PyValueError::new_err)
+}
+
+/// Core of `set_batch_threads`: fails for zero threads.
+pub fn store_batch_threads(threads: usize) -> Result<(), String> {
+    if threads == 0 {
+        return Err("batch thread count must be at least 1".to_string());
+    }
+    BATCH_THREADS.store(threads, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Return the number of worker threads used for large batches.
+#[cfg_attr(feature = "python", pyfunction)]
+pub fn get_batch_threads() -> usize {
+    BATCH_THREADS.load(Ordering::Relaxed)
+}
+
+/// `items.iter().map(f).collect()`, spread over the configured worker
+/// threads once the batch reaches `SERIAL_BATCH_THRESHOLD` items.
+pub(crate) fn map_batch<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    map_on_threads(items, get_batch_threads(), f)
+}
+
+/// `map_batch` with an explicit worker count.
+fn map_on_threads<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    if items.len() < SERIAL_BATCH_THRESHOLD || threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+    let chunk_size = items.len().div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<R>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_batch_keeps_order() {
+        for len in [
+            0,
+            1,
+            SERIAL_BATCH_THRESHOLD - 1,
+            SERIAL_BATCH_THRESHOLD,
+            1000,
+        ] {
+            let items: Vec<usize> = (0..len).collect();
+            let doubled: Vec<usize> = items.iter().map(|n| n * 2).collect();
+            assert_eq!(map_batch(&items, |n| n * 2), doubled);
+            for threads in [1, 3, 8] {
+                assert_eq!(map_on_threads(&items, threads, |n| n * 2), doubled);
+            }
+        }
+    }
+
+    #[test]
+    fn test_batch_threads_setting() {
+        assert!(get_batch_threads() >= 1);
+        // Rejected counts leave the setting untouched.
+        let before = get_batch_threads();
+        assert!(store_batch_threads(0).is_err());
+        assert_eq!(get_batch_threads(), before);
+    }
+}
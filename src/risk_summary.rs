@@ -0,0 +1,160 @@
+//! Cross-module risk verdicts for dashboards and human-review queues.
+
+#[cfg(feature = "python")]
+use std::collections::HashMap;
+
+#[cfg(feature = "python")]
+use pyo3::exceptions::PyValueError;
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+#[cfg(feature = "python")]
+use pyo3::types::PyDict;
+
+use crate::bias_scorer::{self, BiasOptions};
+use crate::injection_detector::{self, ScanOptions};
+use crate::pii_redactor::{placeholder_label, redact, RedactOptions};
+
+/// Injection score at or above which a text counts as risky; matches the
+/// `InjectionDetector` default.
+const DEFAULT_INJECTION_THRESHOLD: f64 = 0.5;
+/// Bias score at or above which a text counts as risky.
+const DEFAULT_BIAS_THRESHOLD: f64 = 0.3;
+
+/// Thresholds for the per-module verdicts.
+pub struct RiskConfig {
+    pub injection_threshold: f64,
+    pub bias_threshold: f64,
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        Self {
+            injection_threshold: DEFAULT_INJECTION_THRESHOLD,
+            bias_threshold: DEFAULT_BIAS_THRESHOLD,
+        }
+    }
+}
+
+impl RiskConfig {
+    /// Build a config from `{"injection_threshold": .., "bias_threshold": ..}`;
+    /// missing keys keep their defaults.
+    #[cfg(feature = "python")]
+    fn from_map(map: HashMap<String, f64>) -> Result<Self, String> {
+        let mut config = Self::default();
+        for (key, value) in map {
+            match key.as_str() {
+                "injection_threshold" => config.injection_threshold = value,
+                "bias_threshold" => config.bias_threshold = value,
+                _ => {
+                    return Err(format!(
+                        "Unknown risk_summary config key '{}'; expected 'injection_threshold' or 'bias_threshold'",
+                        key
+                    ))
+                }
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Each module's verdict on one text.
+pub struct RiskSummary {
+    pub injection: bool,
+    pub injection_score: f64,
+    pub pii: bool,
+    pub pii_labels: Vec<String>,
+    pub bias: bool,
+    pub bias_score: f64,
+}
+
+impl RiskSummary {
+    /// True when every module reached the same verdict.
+    pub fn agreement(&self) -> bool {
+        self.injection == self.pii && self.pii == self.bias
+    }
+}
+
+/// Core of `risk_summary`: run injection, PII and bias checks on `text`.
+pub fn summarize(text: &str, config: &RiskConfig) -> RiskSummary {
+    let injection = injection_detector::score(text, &ScanOptions::default());
+    let (bias, _) = bias_scorer::score(text, &BiasOptions::default());
+    let (_, mapping) = redact(text, &RedactOptions::default());
+    let mut pii_labels: Vec<String> = mapping
+        .keys()
+        .map(|p| placeholder_label(p).to_string())
+        .collect();
+    pii_labels.sort_unstable();
+    pii_labels.dedup();
+    RiskSummary {
+        injection: injection >= config.injection_threshold,
+        injection_score: injection,
+        pii: !pii_labels.is_empty(),
+        pii_labels,
+        bias: bias >= config.bias_threshold,
+        bias_score: bias,
+    }
+}
+
+/// Run injection, PII and bias checks on `text` and report each verdict.
+///
+/// Returns a dict with per-module booleans `injection`, `pii` and `bias`
+/// (`True` = risky), the supporting `injection_score`, `bias_score` and
+/// `pii_labels`, and `agreement`: `True` when all three verdicts match.
+/// Texts without agreement are good candidates for human review.
+///
+/// `config` may set `injection_threshold` (default 0.5) and
+/// `bias_threshold` (default 0.3); other keys raise `ValueError`.
+#[cfg(feature = "python")]
+#[pyfunction]
+#[pyo3(signature = (text, config=None))]
+pub fn risk_summary(
+    py: Python<'_>,
+    text: &str,
+    config: Option<HashMap<String, f64>>,
+) -> PyResult<Py<PyDict>> {
+    let config = RiskConfig::from_map(config.unwrap_or_default()).map_err(PyValueError::new_err)?;
+    let summary = summarize(text, &config);
+    let dict = PyDict::new(py);
+    dict.set_item("injection", summary.injection)?;
+    dict.set_item("pii", summary.pii)?;
+    dict.set_item("bias", summary.bias)?;
+    dict.set_item("agreement", summary.agreement())?;
+    dict.set_item("injection_score", summary.injection_score)?;
+    dict.set_item("bias_score", summary.bias_score)?;
+    dict.set_item("pii_labels", &summary.pii_labels)?;
+    Ok(dict.unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modules_disagree() {
+        let summary = summarize("All women are emotional.", &RiskConfig::default());
+        assert!(summary.bias);
+        assert!(!summary.injection);
+        assert!(!summary.pii);
+        assert!(!summary.agreement());
+    }
+
+    #[test]
+    fn test_clean_text_agrees() {
+        let summary = summarize("The meeting starts at noon.", &RiskConfig::default());
+        assert!(!summary.injection && !summary.pii && !summary.bias);
+        assert!(summary.agreement());
+    }
+
+    #[cfg(feature = "python")]
+    #[test]
+    fn test_config_keys() {
+        let mut map = HashMap::new();
+        map.insert("bias_threshold".to_string(), 0.9);
+        let config = RiskConfig::from_map(map).unwrap();
+        assert!(!summarize("All women are emotional.", &config).bias);
+
+        let mut map = HashMap::new();
+        map.insert("pii_threshold".to_string(), 0.5);
+        assert!(RiskConfig::from_map(map).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Lightweight sentence segmentation shared by the sentence-level checks.
+
+/// Byte spans `(start, end)` of the sentences in `text`, in order.
+///
+/// A sentence ends at `.`, `!` or `?` followed by whitespace (so decimals and
+/// URLs stay intact) or at a line break.  Spans are trimmed of surrounding
+/// whitespace and empty sentences are dropped.
+pub(crate) fn sentence_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let boundary = match c {
+            '\n' => Some(i),
+            '.' | '!' | '?' => match chars.peek() {
+                None => Some(i + 1),
+                Some((_, next)) if next.is_whitespace() => Some(i + 1),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(end) = boundary {
+            push_trimmed(text, start, end, &mut spans);
+            start = end;
+        }
+    }
+    push_trimmed(text, start, text.len(), &mut spans);
+    spans
+}
+
+fn push_trimmed(text: &str, start: usize, end: usize, spans: &mut Vec<(usize, usize)>) {
+    let slice = &text[start..end];
+    let trimmed = slice.trim();
+    if !trimmed.is_empty() {
+        let offset = start + (slice.len() - slice.trim_start().len());
+        spans.push((offset, offset + trimmed.len()));
+    }
+}
+
+/// The sentences of `text`, trimmed, in order.
+pub(crate) fn split_sentences(text: &str) -> Vec<&str> {
+    sentence_spans(text)
+        .into_iter()
+        .map(|(start, end)| &text[start..end])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_sentences() {
+        let text = "Pi is 3.14. Is it?  Yes!\nSee https://example.com/a.b for more";
+        assert_eq!(
+            split_sentences(text),
+            vec![
+                "Pi is 3.14.",
+                "Is it?",
+                "Yes!",
+                "See https://example.com/a.b for more"
+            ]
+        );
+        assert!(split_sentences("  \n ").is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+//! Opt-in per-rule hit counters for tuning rule sets against live traffic.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+
+/// Thread-safe collector of per-label hit counts.
+///
+/// Pass an instance as `stats=` to the injection and PII functions; each call
+/// increments the counter of every rule/label that fired.  Only labels are
+/// recorded -- never the inputs themselves.
+#[cfg_attr(feature = "python", pyclass(frozen))]
+#[derive(Default)]
+pub struct RuleStats {
+    counts: Mutex<HashMap<String, u64>>,
+    calls: AtomicU64,
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl RuleStats {
+    #[new]
+    fn py_new() -> Self {
+        Self::default()
+    }
+
+    /// Return a snapshot of `{label: hit_count}`.
+    #[pyo3(name = "counts")]
+    fn py_counts(&self) -> HashMap<String, u64> {
+        self.counts()
+    }
+
+    /// Number of calls that reported into this collector.
+    #[getter(calls)]
+    fn py_calls(&self) -> u64 {
+        self.calls()
+    }
+
+    /// Clear all counters.
+    #[pyo3(name = "reset")]
+    fn py_reset(&self) {
+        self.reset();
+    }
+}
+
+impl RuleStats {
+    /// Return a snapshot of `{label: hit_count}`.
+    pub fn counts(&self) -> HashMap<String, u64> {
+        self.counts.lock().unwrap().clone()
+    }
+
+    /// Number of calls that reported into this collector.
+    pub fn calls(&self) -> u64 {
+        self.calls.load(Ordering::Relaxed)
+    }
+
+    /// Clear all counters.
+    pub fn reset(&self) {
+        self.counts.lock().unwrap().clear();
+        self.calls.store(0, Ordering::Relaxed);
+    }
+
+    /// Record one call in which each of `labels` fired (repeats count twice).
+    #[cfg(feature = "python")]
+    pub(crate) fn record<'a>(&self, labels: impl IntoIterator<Item = &'a str>) {
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        let mut counts = self.counts.lock().unwrap();
+        for label in labels {
+            *counts.entry(label.to_string()).or_insert(0) += 1;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "python"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_across_calls() {
+        let stats = RuleStats::default();
+        stats.record(["ignore_previous", "reveal_system_prompt"]);
+        stats.record(["ignore_previous"]);
+        stats.record([]);
+
+        let counts = stats.counts();
+        assert_eq!(counts["ignore_previous"], 2);
+        assert_eq!(counts["reveal_system_prompt"], 1);
+        assert_eq!(stats.calls(), 3);
+
+        stats.reset();
+        assert!(stats.counts().is_empty());
+        assert_eq!(stats.calls(), 0);
+    }
+}
@@ -44,3 +44,65 @@ class TestMultipleFlags:
         )
         assert report.score > 0.2
         assert len(report.flags) >= 2
+
+
+class TestValidateGate:
+    def test_over_threshold_fails(self):
+        scorer = BiasScorer()
+        is_valid, issues = scorer.validate("All women are emotional.", threshold=0.3)
+        assert not is_valid
+        assert any(i.rule == "bias" and i.severity == "error" for i in issues)
+
+    def test_neutral_text_passes(self):
+        scorer = BiasScorer()
+        is_valid, issues = scorer.validate("The meeting starts at noon.", threshold=0.3)
+        assert is_valid
+        assert issues == []
+
+
+class TestTokenGroups:
+    def test_custom_groups_imbalance(self):
+        scorer = BiasScorer(
+            token_groups={
+                "young": ["teen", "youth"],
+                "old": ["elder", "retiree", "pensioner"],
+            }
+        )
+        report = scorer.score(
+            "The elder, the retiree and the pensioner spoke; one teen listened."
+        )
+        assert report.score > 0.0
+        assert any("'old' references outnumber 'young'" in f for f in report.flags)
+
+
+class TestLoadedFraming:
+    def test_loaded_adjective_flagged(self):
+        scorer = BiasScorer()
+        report = scorer.score("Those lazy immigrants took the jobs.")
+        assert report.score > 0.0
+        assert any(f.startswith("Loaded framing") for f in report.flags)
+
+    def test_neutral_adjective_ignored(self):
+        report = BiasScorer().score("Hardworking people built this town.")
+        assert report.score == 0.0
+        assert report.flags == []
+
+
+class TestDedupeOverlapping:
+    def test_overlapping_matches_counted_once(self):
+        text = "All women are bad at maths."
+        double = BiasScorer().score(text)
+        single = BiasScorer(dedupe_overlapping=True).score(text)
+        assert len(double.flags) == 2
+        assert len(single.flags) == 1
+        assert single.score < double.score
+
+
+class TestUnifiedScale:
+    def test_ordering_preserved(self):
+        risky, benign = "All women are bad at maths.", "The team shipped on time."
+        raw = BiasScorer()
+        unified = BiasScorer(unified_scale=True)
+        assert raw.score(risky).score > raw.score(benign).score
+        assert unified.score(risky).score > unified.score(benign).score
+        assert unified.score(benign).score == 0.0
@@ -89,3 +89,21 @@ class TestDisabledGuards:
 
         assert result.blocked is False
         assert result.injection.score == 0.0
+
+
+class TestAutoRedactOutput:
+    def test_output_email_is_redacted_in_report(self):
+        pipeline = GuardrailsPipeline(auto_redact_output=True)
+        result = pipeline.post_process("You can reach support at help@example.com.")
+
+        assert "help@example.com" in result.final_text
+        assert result.redacted_output is not None
+        assert "help@example.com" not in result.redacted_output
+        assert "help@example.com" in result.output_pii_mapping.values()
+
+    def test_disabled_by_default(self):
+        pipeline = GuardrailsPipeline()
+        result = pipeline.post_process("You can reach support at help@example.com.")
+
+        assert result.redacted_output is None
+        assert result.output_pii_mapping == {}